@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use erased_cells::{BufferOps, CellBuffer, CellType};
+
+fn add(c: &mut Criterion) {
+    let lhs = CellBuffer::fill_via(1_000_000, |i| (i % 251) as u16);
+    let rhs = CellBuffer::fill_via(1_000_000, |i| (i % 127) as u16);
+    // Mixed cell-types take the per-`CellValue` path.
+    let rhs_mixed = rhs.convert(CellType::UInt32).unwrap();
+    let mut group = c.benchmark_group("add");
+    group.bench_function("same_type", |b| {
+        b.iter(|| black_box(&lhs) + black_box(&rhs))
+    });
+    group.bench_function("mixed_type", |b| {
+        b.iter(|| black_box(&lhs) + black_box(&rhs_mixed))
+    });
+    group.finish();
+}
+
+fn min_max(c: &mut Criterion) {
+    let buf = CellBuffer::fill_via(1_000_000, |i| (i as f32).sin());
+    c.bench_function("min_max", |b| b.iter(|| black_box(&buf).min_max()));
+}
+
+criterion_group!(benches, add, min_max);
+criterion_main!(benches);
@@ -0,0 +1,18 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use erased_cells::{BufferOps, CellBuffer};
+
+fn min_max(c: &mut Criterion) {
+    let buf = CellBuffer::fill_via(1_000_000, |i| (i as f32).sin());
+    let mut group = c.benchmark_group("min_max");
+    group.bench_function("sequential", |b| b.iter(|| black_box(&buf).min_max()));
+    group.bench_function("parallel", |b| b.iter(|| black_box(&buf).par_min_max()));
+    group.finish();
+}
+
+fn sum(c: &mut Criterion) {
+    let buf = CellBuffer::fill_via(1_000_000, |i| (i % 251) as u16);
+    c.bench_function("par_sum", |b| b.iter(|| black_box(&buf).par_sum()));
+}
+
+criterion_group!(benches, min_max, sum);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//! Probes the version of GDAL linked via `gdal-sys` (when the `gdal` feature is enabled),
+//! emitting cfgs for cell-types only supported by newer versions of GDAL.
+//!
+//! These mirror the conditions under which the `gdal` crate itself defines the
+//! corresponding `GdalDataType` variants:
+//! * `gdal_int64`: `Int64` and `UInt64` bands (`major >= 3 && minor >= 5`).
+//! * `gdal_int8`: `Int8` bands (`(major >= 3 && minor >= 7) || major >= 4`).
+
+use std::env;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(gdal_int8, gdal_int64)");
+
+    // Only set by `gdal-sys`, via its `links = "gdal"` metadata.
+    let Ok(version) = env::var("DEP_GDAL_VERSION_NUMBER") else {
+        return;
+    };
+    // Computed as `major * 1000000 + minor * 10000 + patch * 100`.
+    let version: i64 = version
+        .parse()
+        .expect("gdal-sys should emit a numeric version_number");
+    let major = version / 1_000_000;
+    let minor = (version % 1_000_000) / 10_000;
+
+    if major >= 3 && minor >= 5 {
+        println!("cargo:rustc-cfg=gdal_int64");
+    }
+    if (major >= 3 && minor >= 7) || major >= 4 {
+        println!("cargo:rustc-cfg=gdal_int8");
+    }
+}
@@ -1,9 +1,10 @@
 use erased_cells::{with_ct, CellType};
 fn primitive_name(ct: CellType) -> &'static str {
     macro_rules! primitive_name {
-       ($(($id:ident, $p:ident)),*) => {
+       ($(($id:ident, $p:ty)),*) => {
             match ct {
-                $(CellType::$id => stringify!($p),)*
+                $(CellType::$id => std::any::type_name::<$p>(),)*
+                _ => unreachable!(),
             }
        };
     }
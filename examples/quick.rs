@@ -8,5 +8,5 @@ fn main() {
     // Perform element-wise and scalar math. Division coerces buffer to f64.
     let result = buf1 / buf2 * 0.5;
     // Expected result:
-    assert_eq!(result, vec![0.25, 0.25, 0.25].into());
+    assert_eq!(result, vec![0.25, 0.25, 0.25]);
 }
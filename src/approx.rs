@@ -0,0 +1,134 @@
+//! `erased-cells` connector to the `approx` crate.
+//!
+//! Floating-point values are compared via `f64`, while integral values compare exactly.
+
+use approx::{AbsDiffEq, RelativeEq};
+use num_traits::ToPrimitive;
+
+use crate::{BufferOps, CellBuffer, CellValue};
+
+impl CellValue {
+    /// Determine if `self` and `other` are both integral, and should therefore be compared exactly.
+    fn both_integral(&self, other: &Self) -> bool {
+        self.cell_type().is_integral() && other.cell_type().is_integral()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "approx")))]
+impl AbsDiffEq for CellValue {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> Self::Epsilon {
+        f64::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        if self.both_integral(other) {
+            self == other
+        } else {
+            let (l, r) = (self.to_f64().unwrap(), other.to_f64().unwrap());
+            l.abs_diff_eq(&r, epsilon)
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "approx")))]
+impl RelativeEq for CellValue {
+    fn default_max_relative() -> Self::Epsilon {
+        f64::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        if self.both_integral(other) {
+            self == other
+        } else {
+            let (l, r) = (self.to_f64().unwrap(), other.to_f64().unwrap());
+            l.relative_eq(&r, epsilon, max_relative)
+        }
+    }
+}
+
+/// Buffers are compared element-wise, and are never equal if their lengths differ.
+#[cfg_attr(docsrs, doc(cfg(feature = "approx")))]
+impl AbsDiffEq for CellBuffer {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> Self::Epsilon {
+        CellValue::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.len() == other.len()
+            && self
+                .into_iter()
+                .zip(other)
+                .all(|(l, r)| l.abs_diff_eq(&r, epsilon))
+    }
+}
+
+/// Buffers are compared element-wise, and are never equal if their lengths differ.
+#[cfg_attr(docsrs, doc(cfg(feature = "approx")))]
+impl RelativeEq for CellBuffer {
+    fn default_max_relative() -> Self::Epsilon {
+        CellValue::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.len() == other.len()
+            && self
+                .into_iter()
+                .zip(other)
+                .all(|(l, r)| l.relative_eq(&r, epsilon, max_relative))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufferOps, CellBuffer, CellValue};
+    use approx::*;
+
+    #[test]
+    fn values() {
+        assert_abs_diff_eq!(
+            CellValue::Float64(1.0),
+            CellValue::Float32(1.0000001),
+            epsilon = 1e-6
+        );
+        assert_abs_diff_ne!(
+            CellValue::Float64(1.0),
+            CellValue::Float64(1.1),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            CellValue::Float64(1e9),
+            CellValue::Float64(1e9 + 1e-3),
+            max_relative = 1e-9
+        );
+        assert_abs_diff_eq!(CellValue::UInt8(3), CellValue::Int16(3));
+        assert_abs_diff_ne!(CellValue::UInt8(3), CellValue::UInt8(4), epsilon = 2.0);
+    }
+
+    #[test]
+    fn buffers() {
+        let a = CellBuffer::fill_via(10, |i| i as f64 / 3.0);
+        let b = CellBuffer::fill_via(10, |i| (i as f64 / 3.0) as f32);
+        assert_abs_diff_eq!(a, b, epsilon = 1e-6);
+        assert_relative_eq!(a, b, max_relative = 1e-6);
+        assert_abs_diff_ne!(a, b, epsilon = 1e-12);
+        assert_abs_diff_ne!(a, a.slice(1..), epsilon = 1e-6);
+
+        let i = CellBuffer::fill_via(10, |i| i as u16);
+        assert_abs_diff_eq!(i, i.clone());
+        assert_abs_diff_ne!(i, CellBuffer::fill_via(10, |i| i as u16 + 1), epsilon = 2.0);
+    }
+}
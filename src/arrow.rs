@@ -0,0 +1,127 @@
+//! `erased-cells` connector to Apache Arrow.
+
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::*;
+use arrow_array::{Array, ArrayRef, PrimitiveArray};
+use arrow_buffer::NullBuffer;
+use arrow_schema::DataType;
+use paste::paste;
+
+use crate::error::{Error, Result};
+use crate::{with_ct, CellBuffer};
+#[cfg(feature = "masked")]
+use crate::{Mask, MaskedCellBuffer};
+
+impl CellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    /// Construct a [`CellBuffer`] from an Arrow primitive array.
+    ///
+    /// Any null values in `array` are ignored; see `MaskedCellBuffer::from_arrow` for
+    /// null-aware conversion.
+    ///
+    /// Returns `Err(UnsupportedCellTypeError)` if `array` is not of a supported primitive type.
+    pub fn from_arrow(array: &dyn Array) -> Result<Self> {
+        macro_rules! from_arrow {
+            ( $(($id:ident, $_p:ty)),*) => { paste! {
+                match array.data_type() {
+                    $(DataType::$id => Ok(CellBuffer::$id(
+                        array.as_primitive::<[<$id Type>]>().values().to_vec(),
+                    )),)*
+                    o => Err(Error::UnsupportedCellTypeError(o.to_string())),
+                }
+            }};
+        }
+        with_ct!(from_arrow)
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    /// Convert `self` into an Arrow primitive array, without nulls.
+    pub fn to_arrow(&self) -> ArrayRef {
+        self.to_arrow_with_nulls(None)
+    }
+
+    fn to_arrow_with_nulls(&self, nulls: Option<NullBuffer>) -> ArrayRef {
+        macro_rules! to_arrow {
+            ( $(($id:ident, $_p:ty)),*) => { paste! {
+                match self {
+                    $(CellBuffer::$id(v) => Arc::new(
+                        PrimitiveArray::<[<$id Type>]>::new(v.clone().into(), nulls),
+                    ),)*
+                }
+            }};
+        }
+        with_ct!(to_arrow)
+    }
+}
+
+#[cfg(feature = "masked")]
+impl MaskedCellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "arrow", feature = "masked"))))]
+    /// Construct a [`MaskedCellBuffer`] from an Arrow primitive array, with the
+    /// [`Mask`] derived from the array's validity bitmap.
+    ///
+    /// Returns `Err(UnsupportedCellTypeError)` if `array` is not of a supported primitive type.
+    pub fn from_arrow(array: &dyn Array) -> Result<Self> {
+        let buffer = CellBuffer::from_arrow(array)?;
+        let mask = match array.logical_nulls() {
+            Some(nulls) => Mask::new(nulls.iter().collect()),
+            None => Mask::fill(array.len(), true),
+        };
+        Ok(Self::new(buffer, mask))
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "arrow", feature = "masked"))))]
+    /// Convert `self` into an Arrow primitive array, with the [`Mask`] encoded as
+    /// the array's validity bitmap.
+    pub fn to_arrow(&self) -> ArrayRef {
+        let nulls = NullBuffer::from_iter(self.mask().clone());
+        let nulls = (nulls.null_count() > 0).then_some(nulls);
+        self.buffer().to_arrow_with_nulls(nulls)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BufferOps, CellType};
+    use arrow_array::{Float64Array, StringArray, UInt8Array};
+
+    #[test]
+    fn primitives() {
+        let a = UInt8Array::from(vec![1, 2, 3]);
+        let buf = CellBuffer::from_arrow(&a).unwrap();
+        assert_eq!(buf, CellBuffer::from_vec(vec![1u8, 2, 3]));
+        assert_eq!(buf.to_arrow().as_primitive::<UInt8Type>(), &a);
+
+        let a = Float64Array::from(vec![1.5, -2.5]);
+        let buf = CellBuffer::from_arrow(&a).unwrap();
+        assert_eq!(buf.cell_type(), CellType::Float64);
+        assert_eq!(buf.to_arrow().as_primitive::<Float64Type>(), &a);
+
+        for ct in CellType::iter() {
+            let buf = CellBuffer::fill(3, ct.max_value());
+            assert_eq!(CellBuffer::from_arrow(&buf.to_arrow()).unwrap(), buf);
+        }
+
+        assert!(CellBuffer::from_arrow(&StringArray::from(vec!["a"])).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "masked")]
+    fn masked() {
+        let a = Float64Array::from(vec![Some(1.0), None, Some(3.0)]);
+        let buf = MaskedCellBuffer::from_arrow(&a).unwrap();
+        assert_eq!(buf.mask(), &Mask::new(vec![true, false, true]));
+        assert_eq!(buf.get_masked(2), Some(3.0.into()));
+
+        let r = buf.to_arrow();
+        assert_eq!(r.null_count(), 1);
+        assert!(r.is_null(1));
+        assert_eq!(MaskedCellBuffer::from_arrow(&r).unwrap(), buf);
+
+        let buf = MaskedCellBuffer::from_vec(vec![1u8, 2]);
+        assert!(buf.to_arrow().nulls().is_none());
+    }
+}
@@ -1,16 +1,20 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Debug, Formatter};
+use std::ops::{Bound, Deref, RangeBounds};
 
-use num_traits::ToPrimitive;
-use paste::paste;
+use num_traits::{NumCast, ToPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
 use crate::{with_ct, BufferOps, CellEncoding, CellType, CellValue};
+#[cfg(feature = "f16")]
+use half::f16;
 
 /// CellBuffer enum constructor.
 macro_rules! cb_enum {
-    ( $(($id:ident, $p:ident)),*) => {
+    ( $(($id:ident, $p:ty)),*) => {
         /// An enum over buffers of [`CellEncoding`] types.
         ///
         /// See [module documentation](crate#example) for usage example.
@@ -47,6 +51,9 @@ macro_rules! cb_enum {
         /// let diff = buf2 - buf1;
         /// assert_eq!(diff.min_max(), ((-8).into(), 8.into()));
         /// ```
+        ///
+        /// Non-exhaustive, as with [`CellType`].
+        #[non_exhaustive]
         #[derive(Clone)]
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         pub enum CellBuffer { $($id(Vec<$p>)),* }
@@ -58,6 +65,764 @@ impl CellBuffer {
     pub fn new<T: CellEncoding>(data: Vec<T>) -> Self {
         data.into()
     }
+
+    /// Construct an empty buffer of cell-type `ct` with space for at least `capacity` cells.
+    pub fn with_capacity(capacity: usize, ct: CellType) -> Self {
+        macro_rules! with_capacity {
+            ( $(($id:ident, $p:ty)),*) => {
+                match ct {
+                    $(CellType::$id => CellBuffer::$id(Vec::<$p>::with_capacity(capacity)),)*
+                }
+            };
+        }
+        with_ct!(with_capacity)
+    }
+
+    /// Construct a buffer of cell-type `ct` directly from the raw components of a `Vec`,
+    /// adopting the memory without copying.
+    ///
+    /// `len` and `capacity` are counts of cells, not bytes.
+    ///
+    /// # Safety
+    ///
+    /// The contract of [`Vec::from_raw_parts`] applies, with `T` being the primitive of `ct`.
+    /// In particular:
+    ///
+    /// * `ptr` must have been allocated by the global allocator, with the alignment of `ct`'s
+    ///   primitive and a size of `capacity * ct.size_of()` bytes.
+    /// * The first `len` cells (`len * ct.size_of()` bytes) must be initialized.
+    /// * Ownership of the allocation is transferred to the returned buffer, which will
+    ///   free it on drop; `ptr` must not be used or freed elsewhere afterward.
+    pub unsafe fn from_raw_parts(
+        ptr: *mut u8,
+        len: usize,
+        capacity: usize,
+        ct: CellType,
+    ) -> CellBuffer {
+        macro_rules! from_raw_parts {
+            ( $(($id:ident, $p:ty)),*) => {
+                match ct {
+                    $(CellType::$id => {
+                        debug_assert!(ptr.cast::<$p>().is_aligned());
+                        CellBuffer::$id(Vec::from_raw_parts(ptr.cast::<$p>(), len, capacity))
+                    })*
+                }
+            };
+        }
+        with_ct!(from_raw_parts)
+    }
+
+    /// Get the number of cells the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        macro_rules! capacity {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.capacity(),)*
+                }
+            };
+        }
+        with_ct!(capacity)
+    }
+
+    /// Reserve capacity for at least `additional` more cells.
+    pub fn reserve(&mut self, additional: usize) {
+        macro_rules! reserve {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.reserve(additional),)*
+                }
+            };
+        }
+        with_ct!(reserve)
+    }
+
+    /// Concatenate `buffers` into a single buffer.
+    ///
+    /// The resulting [`CellType`] is the [union][CellType::union] of all the inputs' cell-types.
+    /// An empty `buffers` slice results in an empty `UInt8` buffer.
+    pub fn concat(buffers: &[CellBuffer]) -> Result<CellBuffer> {
+        let ct = CellType::common(buffers.iter().map(|b| b.cell_type())).unwrap_or(CellType::UInt8);
+        let len = buffers.iter().map(|b| b.len()).sum();
+        let mut result = CellBuffer::with_capacity(len, ct);
+        for b in buffers {
+            result.append(b)?;
+        }
+        Ok(result)
+    }
+
+    /// Append the contents of `other` to `self`.
+    ///
+    /// If necessary, `self` is first converted to the [union][CellType::union] of
+    /// both buffers' cell-types. See [`Self::extend_from_buffer`] to instead preserve
+    /// `self.cell_type()`.
+    pub fn append(&mut self, other: &CellBuffer) -> Result<()> {
+        let ct = self.cell_type().union(other.cell_type());
+        if ct != self.cell_type() {
+            *self = self.convert(ct)?;
+        }
+        self.extend_from_buffer(other)
+    }
+
+    /// Append the contents of `other` to `self`, converting `other` to `self.cell_type()`.
+    ///
+    /// Returns `Err(NarrowingError)`, leaving `self` unchanged, if `other.cell_type()`
+    /// cannot be converted to `self.cell_type()` without data loss. See [`Self::append`]
+    /// to instead widen `self`.
+    pub fn extend_from_buffer(&mut self, other: &CellBuffer) -> Result<()> {
+        let other = other.convert(self.cell_type())?;
+        macro_rules! extend {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match (self, other) {
+                    $((CellBuffer::$id(l), CellBuffer::$id(r)) => l.extend_from_slice(&r),)*
+                    _ => unreachable!(),
+                }
+            };
+        }
+        with_ct!(extend);
+        Ok(())
+    }
+
+    /// Append `value` to the end of the buffer.
+    ///
+    /// Returns `Err(NarrowingError)` if `value.cell_type() != self.cell_type()`
+    /// and overflow could occur.
+    pub fn push(&mut self, value: CellValue) -> Result<()> {
+        let value = value.convert(self.cell_type())?;
+        macro_rules! push {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match (self, value) {
+                    $((CellBuffer::$id(b), CellValue::$id(v)) => b.push(v),)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+        with_ct!(push);
+        Ok(())
+    }
+
+    /// Resize the buffer in-place to `new_len`, either truncating, or filling new cells with `value`.
+    ///
+    /// Returns `Err(NarrowingError)` if `value.cell_type() != self.cell_type()`
+    /// and overflow could occur.
+    pub fn resize(&mut self, new_len: usize, value: CellValue) -> Result<()> {
+        let value = value.convert(self.cell_type())?;
+        macro_rules! resize {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match (self, value) {
+                    $((CellBuffer::$id(b), CellValue::$id(v)) => b.resize(new_len, v),)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+        with_ct!(resize);
+        Ok(())
+    }
+
+    /// Check the buffer for invalid contents, as a debugging aid when the buffer has
+    /// been populated through FFI or other `unsafe` means.
+    ///
+    /// Returns `Err(ValidationError)` if a floating-point buffer contains a signaling `NaN`.
+    pub fn validate(&self) -> Result<()> {
+        let signaling = match self {
+            #[cfg(feature = "f16")]
+            CellBuffer::Float16(v) => v
+                .iter()
+                .position(|f| f.is_nan() && f.to_bits() & 0x0200 == 0),
+            CellBuffer::Float32(v) => v
+                .iter()
+                .position(|f| f.is_nan() && f.to_bits() & 0x0040_0000 == 0),
+            CellBuffer::Float64(v) => v
+                .iter()
+                .position(|f| f.is_nan() && f.to_bits() & 0x0008_0000_0000_0000 == 0),
+            _ => None,
+        };
+        match signaling {
+            Some(idx) => Err(Error::ValidationError(format!(
+                "signaling NaN at index {idx}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Replace non-finite floating-point cells with the given substitutes.
+    ///
+    /// See [`CellValue::nan_to_num`].
+    pub fn nan_to_num(&self, nan: f64, posinf: f64, neginf: f64) -> CellBuffer {
+        let sub = |v: CellValue| v.nan_to_num(nan, posinf, neginf);
+        match self {
+            #[cfg(feature = "f16")]
+            CellBuffer::Float16(v) => {
+                CellBuffer::Float16(v.iter().map(|c| sub((*c).into()).get().unwrap()).collect())
+            }
+            CellBuffer::Float32(v) => {
+                CellBuffer::Float32(v.iter().map(|c| sub((*c).into()).get().unwrap()).collect())
+            }
+            CellBuffer::Float64(v) => {
+                CellBuffer::Float64(v.iter().map(|c| sub((*c).into()).get().unwrap()).collect())
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Restrict each cell in-place to the inclusive range `[min, max]`.
+    ///
+    /// `NaN` cells are left unchanged. Named to avoid resolving to [`Ord::clamp`], which
+    /// compares whole buffers.
+    ///
+    /// Returns `Err(NarrowingError)` if the value of `min` or `max` is not representable in
+    /// `self.cell_type()`, or `Err(ValidationError)` if `min > max`.
+    pub fn clamp_to(&mut self, min: CellValue, max: CellValue) -> Result<()> {
+        let ct = self.cell_type();
+        let (min, max) = (convert_exact(min, ct)?, convert_exact(max, ct)?);
+        if min > max {
+            return Err(Error::ValidationError(format!(
+                "clamp range minimum {min} is greater than maximum {max}"
+            )));
+        }
+        macro_rules! clamp {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match (self, min, max) {
+                    $((CellBuffer::$id(v), CellValue::$id(lo), CellValue::$id(hi)) => {
+                        v.iter_mut().for_each(|c| {
+                            if *c < lo {
+                                *c = lo
+                            } else if *c > hi {
+                                *c = hi
+                            }
+                        })
+                    })*
+                    _ => unreachable!(),
+                }
+            };
+        }
+        with_ct!(clamp);
+        Ok(())
+    }
+
+    /// Create a copy of the buffer with each cell restricted to the inclusive range `[min, max]`.
+    ///
+    /// See [`Self::clamp_to`].
+    pub fn clamped(&self, min: CellValue, max: CellValue) -> Result<CellBuffer> {
+        let mut r = self.clone();
+        r.clamp_to(min, max)?;
+        Ok(r)
+    }
+
+    /// Map each cell to the output of the first entry in `table` whose input equals the
+    /// cell, comparing after unifying cell-types.
+    ///
+    /// Cells without a matching entry are set to `default`, or left unchanged if `default`
+    /// is `None`. The result has the [`union`][CellType::union] of the cell-types of the
+    /// outputs in `table` and `default`, or `self.cell_type()` if there are none.
+    ///
+    /// Returns `Err(NarrowingError)` if an unchanged cell is not representable in the
+    /// result cell-type.
+    pub fn reclassify(
+        &self,
+        table: &[(CellValue, CellValue)],
+        default: Option<CellValue>,
+    ) -> Result<CellBuffer> {
+        let outputs = table.iter().map(|(_, o)| o).chain(default.as_ref());
+        let ct = CellType::common(outputs.map(CellValue::cell_type)).unwrap_or(self.cell_type());
+        let mut r = CellBuffer::with_capacity(self.len(), ct);
+        for v in self {
+            match table
+                .iter()
+                .find(|(i, _)| *i == v)
+                .map(|(_, o)| *o)
+                .or(default)
+            {
+                Some(o) => r.push(o)?,
+                None => r.push(convert_exact(v, ct)?)?,
+            }
+        }
+        Ok(r)
+    }
+
+    /// Count the `NaN` cells. Always `0` for integral cell-types.
+    pub fn count_nans(&self) -> usize {
+        match self {
+            #[cfg(feature = "f16")]
+            CellBuffer::Float16(v) => v.iter().filter(|c| c.is_nan()).count(),
+            CellBuffer::Float32(v) => v.iter().filter(|c| c.is_nan()).count(),
+            CellBuffer::Float64(v) => v.iter().filter(|c| c.is_nan()).count(),
+            _ => 0,
+        }
+    }
+
+    /// Count the non-finite (`NaN` or infinite) cells. Always `0` for integral cell-types.
+    pub fn count_nonfinite(&self) -> usize {
+        match self {
+            #[cfg(feature = "f16")]
+            CellBuffer::Float16(v) => v.iter().filter(|c| !c.is_finite()).count(),
+            CellBuffer::Float32(v) => v.iter().filter(|c| !c.is_finite()).count(),
+            CellBuffer::Float64(v) => v.iter().filter(|c| !c.is_finite()).count(),
+            _ => 0,
+        }
+    }
+
+    /// Reinterpret the raw bits of each cell as cell-type `ct`, without numeric conversion.
+    ///
+    /// Unlike [`BufferOps::convert`], `UInt32(0x3f800000)` reinterpreted as `Float32` is `1.0`.
+    ///
+    /// Returns `Err(ReinterpretError)` if `ct.size_of()` differs from that of `self.cell_type()`.
+    pub fn reinterpret(&self, ct: CellType) -> Result<CellBuffer> {
+        let src = self.cell_type();
+        if src.size_of() != ct.size_of() {
+            return Err(Error::ReinterpretError { src, dst: ct });
+        }
+        Self::from_le_bytes(ct, &self.to_le_bytes())
+    }
+
+    /// Encode the buffer's cells as a sequence of little-endian bytes.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        macro_rules! to_bytes {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.iter().flat_map(|c| c.to_le_bytes()).collect(),)*
+                }
+            };
+        }
+        with_ct!(to_bytes)
+    }
+
+    /// Encode the buffer's cells as a sequence of big-endian bytes.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        macro_rules! to_bytes {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.iter().flat_map(|c| c.to_be_bytes()).collect(),)*
+                }
+            };
+        }
+        with_ct!(to_bytes)
+    }
+
+    /// Decode a buffer of cell-type `ct` from a sequence of little-endian bytes.
+    ///
+    /// Returns `Err(ByteLengthError)` if `bytes.len()` is not a multiple of `ct.size_of()`.
+    pub fn from_le_bytes(ct: CellType, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() % ct.size_of() != 0 {
+            return Err(Error::ByteLengthError { ct, len: bytes.len() });
+        }
+        macro_rules! from_bytes {
+            ( $(($id:ident, $p:ty)),*) => {
+                match ct {
+                    $(CellType::$id => CellBuffer::$id(
+                        bytes
+                            .chunks_exact(ct.size_of())
+                            .map(|c| <$p>::from_le_bytes(c.try_into().unwrap()))
+                            .collect(),
+                    ),)*
+                }
+            };
+        }
+        Ok(with_ct!(from_bytes))
+    }
+
+    /// Decode a buffer of cell-type `ct` from a sequence of big-endian bytes.
+    ///
+    /// Returns `Err(ByteLengthError)` if `bytes.len()` is not a multiple of `ct.size_of()`.
+    pub fn from_be_bytes(ct: CellType, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() % ct.size_of() != 0 {
+            return Err(Error::ByteLengthError { ct, len: bytes.len() });
+        }
+        macro_rules! from_bytes {
+            ( $(($id:ident, $p:ty)),*) => {
+                match ct {
+                    $(CellType::$id => CellBuffer::$id(
+                        bytes
+                            .chunks_exact(ct.size_of())
+                            .map(|c| <$p>::from_be_bytes(c.try_into().unwrap()))
+                            .collect(),
+                    ),)*
+                }
+            };
+        }
+        Ok(with_ct!(from_bytes))
+    }
+
+    /// Borrow the underlying cells as a `&[T]`, without copying.
+    ///
+    /// Returns `Err(NarrowingError)` if `T::cell_type() != self.cell_type()`.
+    pub fn as_slice<T: CellEncoding>(&self) -> Result<&[T]> {
+        if T::cell_type() != self.cell_type() {
+            return Err(Error::NarrowingError {
+                src: self.cell_type(),
+                dst: T::cell_type(),
+            });
+        }
+        macro_rules! as_slice {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => Ok(danger::cast_slice(v)),)*
+                }
+            };
+        }
+        with_ct!(as_slice)
+    }
+
+    /// Mutably borrow the underlying cells as a `&mut [T]`, without copying.
+    ///
+    /// Returns `Err(NarrowingError)` if `T::cell_type() != self.cell_type()`.
+    pub fn as_mut_slice<T: CellEncoding>(&mut self) -> Result<&mut [T]> {
+        if T::cell_type() != self.cell_type() {
+            return Err(Error::NarrowingError {
+                src: self.cell_type(),
+                dst: T::cell_type(),
+            });
+        }
+        macro_rules! as_mut_slice {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => Ok(danger::cast_slice_mut(v)),)*
+                }
+            };
+        }
+        with_ct!(as_mut_slice)
+    }
+
+    /// Create a new buffer containing a copy of the cells within `range`.
+    ///
+    /// # Panics
+    /// Will panic if `range` is out of bounds, as with standard slicing.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> CellBuffer {
+        let bounds: (Bound<usize>, Bound<usize>) =
+            (range.start_bound().cloned(), range.end_bound().cloned());
+        macro_rules! slice {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => CellBuffer::$id(v[bounds].to_vec()),)*
+                }
+            };
+        }
+        with_ct!(slice)
+    }
+
+    /// Divide the buffer into two copies at index `mid`.
+    ///
+    /// The first contains cells `[0, mid)` and the second `[mid, len)`.
+    ///
+    /// # Panics
+    /// Will panic if `mid > self.len()`, as with [`slice::split_at`].
+    pub fn split_at(&self, mid: usize) -> (CellBuffer, CellBuffer) {
+        assert!(mid <= self.len(), "mid > len");
+        (self.slice(..mid), self.slice(mid..))
+    }
+
+    /// Iterate over successive sub-buffers of `size` cells, preserving [`CellType`].
+    ///
+    /// The last chunk will be shorter than `size` if `size` does not evenly divide `self.len()`.
+    ///
+    /// # Panics
+    /// Will panic if `size` is zero.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = CellBuffer> + '_ {
+        assert!(size != 0, "chunk size must be non-zero");
+        let len = self.len();
+        (0..len)
+            .step_by(size)
+            .map(move |start| self.slice(start..(start + size).min(len)))
+    }
+
+    /// Count the number of occurrences of each distinct value in the buffer.
+    pub fn value_counts(&self) -> BTreeMap<CellValue, usize> {
+        value_counts(self)
+    }
+
+    /// Count the cells within the inclusive range `[min, max]`, comparing after unifying
+    /// cell-types.
+    pub fn count_in_range(&self, min: CellValue, max: CellValue) -> usize {
+        self.into_iter().filter(|v| (min..=max).contains(v)).count()
+    }
+
+    /// Count the cells outside the inclusive range `[min, max]`.
+    ///
+    /// See [`Self::count_in_range`].
+    pub fn count_out_of_range(&self, min: CellValue, max: CellValue) -> usize {
+        self.len() - self.count_in_range(min, max)
+    }
+
+    /// Compute the Shannon entropy, in bits, of the distribution of values in the buffer.
+    ///
+    /// Returns `0.0` for an empty buffer.
+    pub fn shannon_entropy(&self) -> f64 {
+        entropy_per_class(&self.value_counts()).values().sum()
+    }
+
+    /// Compute each distinct value's contribution (`-p log₂ p`) to the
+    /// [Shannon entropy][Self::shannon_entropy] of the buffer.
+    pub fn entropy_per_class(&self) -> BTreeMap<CellValue, f64> {
+        entropy_per_class(&self.value_counts())
+    }
+
+    /// Compute the dot product `Σ aᵢ·bᵢ` of `self` and `other`.
+    ///
+    /// The result has the [unified][CellType::union] cell-type of `self` and `other`. Integral
+    /// dot products are exact, except that one overflowing the unified cell-type is instead
+    /// computed as a `Float64`.
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffers differ in length.
+    pub fn dot(&self, other: &CellBuffer) -> Result<CellValue> {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: other.len() });
+        }
+        let ct = self.cell_type().union(other.cell_type());
+        Ok(dot(ct, self.into_iter().zip(other)))
+    }
+
+    /// Compute the Pearson correlation coefficient between `self` and `other`.
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffers are not the same length. The
+    /// result is `NaN` if either buffer has zero variance.
+    pub fn pearson(&self, other: &CellBuffer) -> Result<f64> {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: other.len() });
+        }
+        Ok(pearson(self.into_iter().zip(other)))
+    }
+
+    /// Determine if `self` and `other` have the same length and all cells are equal within
+    /// `epsilon`, for tolerating rounding differences in floating-point results.
+    ///
+    /// Cells are compared with [`CellValue::approx_eq`].
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.len() == other.len()
+            && self
+                .into_iter()
+                .zip(other)
+                .all(|(l, r)| l.approx_eq(&r, epsilon))
+    }
+
+    /// Combine `self` and `other` element-wise with `f`.
+    ///
+    /// Results are collected per `FromIterator<CellValue>`, so `f` should return values of a
+    /// consistent cell-type (e.g. by [unifying][CellValue::unify] its arguments).
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffers differ in length.
+    pub fn zip_with<F>(&self, other: &CellBuffer, f: F) -> Result<CellBuffer>
+    where
+        F: Fn(CellValue, CellValue) -> CellValue,
+    {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: other.len() });
+        }
+        Ok(self.into_iter().zip(other).map(|(l, r)| f(l, r)).collect())
+    }
+
+    /// Linearly interpolate element-wise between `self` and `other` via [`CellValue::lerp`],
+    /// resulting in a `Float64` buffer.
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffers differ in length.
+    pub fn lerp(&self, other: &CellBuffer, t: f64) -> Result<CellBuffer> {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: other.len() });
+        }
+        Ok(CellBuffer::Float64(
+            self.into_iter()
+                .zip(other)
+                .map(|(a, b)| CellValue::lerp(a, b, t).to_f64().unwrap())
+                .collect(),
+        ))
+    }
+
+    /// Apply the linear transform `cell * scale + offset` element-wise, resulting in a
+    /// `Float64` buffer.
+    ///
+    /// This matches GDAL's conversion from raw to physical values using band scale/offset metadata.
+    pub fn scale_offset(&self, scale: f64, offset: f64) -> CellBuffer {
+        CellBuffer::Float64(
+            self.into_iter()
+                .map(|v| v.to_f64().unwrap() * scale + offset)
+                .collect(),
+        )
+    }
+
+    /// Apply `f` to each cell, collecting the results directly into a `Vec<T>`.
+    pub fn map_into<T: CellEncoding, F: Fn(CellValue) -> T>(self, f: F) -> Vec<T> {
+        self.into_iter().map(f).collect()
+    }
+
+    /// Create a new buffer of the same [`CellType`] containing only the cells for which
+    /// `f` returns `true`.
+    pub fn filter<F: Fn(CellValue) -> bool>(&self, f: F) -> CellBuffer {
+        macro_rules! filter {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => CellBuffer::$id(
+                        v.iter().copied().filter(|x| f((*x).into())).collect()
+                    ),)*
+                }
+            };
+        }
+        with_ct!(filter)
+    }
+
+    /// Render each cell as a `String`, via [`CellValue`]'s `Display` implementation.
+    pub fn to_string_vec(&self) -> Vec<String> {
+        self.into_iter().map(|v| v.to_string()).collect()
+    }
+
+    /// Swap the cells at positions `a` and `b`, as with [`slice::swap`].
+    ///
+    /// # Panics
+    /// Will panic if `a` or `b` are out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        macro_rules! swap {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.swap(a, b),)*
+                }
+            };
+        }
+        with_ct!(swap)
+    }
+
+    /// Sort the buffer in-place, in ascending order.
+    ///
+    /// Floating-point cells are ordered with `total_cmp`, consistent with [`Ord`] for
+    /// `CellBuffer`: positive `NaN`s sort after `+∞`, and negative `NaN`s before `-∞`.
+    pub fn sort(&mut self) {
+        match self {
+            CellBuffer::UInt8(v) => v.sort_unstable(),
+            CellBuffer::UInt16(v) => v.sort_unstable(),
+            CellBuffer::UInt32(v) => v.sort_unstable(),
+            CellBuffer::UInt64(v) => v.sort_unstable(),
+            CellBuffer::Int8(v) => v.sort_unstable(),
+            CellBuffer::Int16(v) => v.sort_unstable(),
+            CellBuffer::Int32(v) => v.sort_unstable(),
+            CellBuffer::Int64(v) => v.sort_unstable(),
+            #[cfg(feature = "f16")]
+            CellBuffer::Float16(v) => v.sort_unstable_by(f16::total_cmp),
+            CellBuffer::Float32(v) => v.sort_unstable_by(f32::total_cmp),
+            CellBuffer::Float64(v) => v.sort_unstable_by(f64::total_cmp),
+        }
+    }
+
+    /// Create a sorted copy of the buffer.
+    ///
+    /// See [`Self::sort`].
+    pub fn sorted(&self) -> CellBuffer {
+        let mut r = self.clone();
+        r.sort();
+        r
+    }
+
+    /// Create a buffer of the distinct values in `self`, in first-seen order.
+    ///
+    /// Values are compared with [`CellValue`] equality, so floating-point `NaN`s with the
+    /// same bit pattern are considered equal, while `-0.0` and `+0.0` are distinct.
+    pub fn unique(&self) -> CellBuffer {
+        let mut seen = HashSet::new();
+        macro_rules! unique {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => CellBuffer::$id(
+                        v.iter().filter(|c| seen.insert(CellValue::$id(**c))).copied().collect(),
+                    ),)*
+                }
+            };
+        }
+        with_ct!(unique)
+    }
+
+    /// Remove consecutive repeated values, as with [`Vec::dedup`].
+    ///
+    /// See [`Self::unique`] for equality semantics.
+    pub fn dedup(&mut self) {
+        macro_rules! dedup {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.dedup_by(|a, b| CellValue::$id(*a) == CellValue::$id(*b)),)*
+                }
+            };
+        }
+        with_ct!(dedup)
+    }
+
+    /// Get the index of the first minimum value, or `None` if the buffer is empty.
+    ///
+    /// Values are ordered as in [`BufferOps::min_max`], comparing floating-point values
+    /// with `total_cmp`, so a negative `NaN` is the minimum if present.
+    pub fn argmin(&self) -> Option<usize> {
+        macro_rules! argmin {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => arg_by(v.iter().copied().enumerate(), Ordering::Less, TotalOrd::total_cmp),)*
+                }
+            };
+        }
+        with_ct!(argmin)
+    }
+
+    /// Get the index of the first maximum value, or `None` if the buffer is empty.
+    ///
+    /// Values are ordered as in [`BufferOps::min_max`], comparing floating-point values
+    /// with `total_cmp`, so a positive `NaN` is the maximum if present.
+    pub fn argmax(&self) -> Option<usize> {
+        macro_rules! argmax {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => arg_by(v.iter().copied().enumerate(), Ordering::Greater, TotalOrd::total_cmp),)*
+                }
+            };
+        }
+        with_ct!(argmax)
+    }
+
+    /// Get a mutable reference to the cell at position `index`, for read-modify-write access.
+    ///
+    /// # Panics
+    /// Will panic if `index` >= `self.len()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erased_cells::{BufferOps, CellBuffer};
+    /// let mut buf = CellBuffer::from_vec(vec![1u8, 2, 3]);
+    /// let mut cell = buf.cell_mut(1);
+    /// cell.set(*cell * 2).unwrap();
+    /// assert_eq!(buf, vec![1u8, 4, 3]);
+    /// ```
+    pub fn cell_mut(&mut self, index: usize) -> CellRefMut<'_> {
+        let value = self.get(index);
+        CellRefMut { buf: self, idx: index, value }
+    }
+
+    /// Compute the count, range, mean, standard deviation and median of the buffer's values.
+    ///
+    /// See [`BufferStats`].
+    pub fn summary(&self) -> BufferStats {
+        summary(self, self.len())
+    }
+
+    /// Compute the `q`-th percentile of the buffer's values, with `q` in `[0, 100]`,
+    /// linearly interpolating between the nearest ranks, as a `Float64` [`CellValue`].
+    ///
+    /// As values are ordered per [`Self::sort`], any positive `NaN`s are treated as the
+    /// largest values. Returns `NaN` for an empty buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is outside `[0, 100]`.
+    pub fn percentile(&self, q: f64) -> CellValue {
+        assert!(
+            (0.0..=100.0).contains(&q),
+            "Percentile {q} outside [0, 100]"
+        );
+        if self.is_empty() {
+            return CellValue::Float64(f64::NAN);
+        }
+        let sorted = self.sorted();
+        let rank = q / 100.0 * (sorted.len() - 1) as f64;
+        let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+        let lv = sorted.get(lo).to_f64().unwrap();
+        let hv = sorted.get(hi).to_f64().unwrap();
+        let r = if lo == hi {
+            lv
+        } else {
+            lv + (hv - lv) * (rank - lo as f64)
+        };
+        CellValue::Float64(r)
+    }
 }
 
 impl BufferOps for CellBuffer {
@@ -67,9 +832,9 @@ impl BufferOps for CellBuffer {
 
     fn with_defaults(len: usize, ct: CellType) -> Self {
         macro_rules! empty {
-            ( $(($id:ident, $p:ident)),*) => {
+            ( $(($id:ident, $p:ty)),*) => {
                 match ct {
-                    $(CellType::$id => Self::from_vec(vec![$p::default(); len]),)*
+                    $(CellType::$id => Self::from_vec(vec![<$p>::default(); len]),)*
                 }
             };
         }
@@ -78,7 +843,7 @@ impl BufferOps for CellBuffer {
 
     fn fill(len: usize, value: CellValue) -> Self {
         macro_rules! empty {
-            ( $(($id:ident, $p:ident)),*) => {
+            ( $(($id:ident, $p:ty)),*) => {
                 match value.cell_type() {
                     $(CellType::$id => Self::from_vec::<$p>(vec![value.get().unwrap(); len]),)*
                 }
@@ -98,7 +863,7 @@ impl BufferOps for CellBuffer {
 
     fn len(&self) -> usize {
         macro_rules! len {
-            ( $(($id:ident, $_p:ident)),*) => {
+            ( $(($id:ident, $_p:ty)),*) => {
                 match self {
                     $(CellBuffer::$id(v) => v.len(),)*
                 }
@@ -113,7 +878,7 @@ impl BufferOps for CellBuffer {
 
     fn cell_type(&self) -> CellType {
         macro_rules! ct {
-            ( $(($id:ident, $_p:ident)),*) => {
+            ( $(($id:ident, $_p:ty)),*) => {
                 match self {
                     $(CellBuffer::$id(_) => CellType::$id,)*
                 }
@@ -124,7 +889,7 @@ impl BufferOps for CellBuffer {
 
     fn get(&self, index: usize) -> CellValue {
         macro_rules! get {
-            ( $(($id:ident, $_p:ident)),*) => {
+            ( $(($id:ident, $_p:ty)),*) => {
                 match self {
                     $(CellBuffer::$id(b) => CellValue::$id(b[index]),)*
                 }
@@ -136,7 +901,7 @@ impl BufferOps for CellBuffer {
     fn put(&mut self, idx: usize, value: CellValue) -> Result<()> {
         let value = value.convert(self.cell_type())?;
         macro_rules! put {
-            ( $(($id:ident, $_p:ident)),*) => {
+            ( $(($id:ident, $_p:ty)),*) => {
                 match (self, value) {
                     $((CellBuffer::$id(b), CellValue::$id(v)) => b[idx] = v,)*
                     _ => unreachable!(),
@@ -158,24 +923,45 @@ impl BufferOps for CellBuffer {
             return Err(err());
         }
 
-        let r: CellBuffer = self
-            .into_iter()
-            .map(|v| v.convert(cell_type).unwrap())
-            .collect();
-
-        Ok(r)
+        // Construct the typed result directly, so empty buffers still get `cell_type`.
+        macro_rules! convert {
+            ( $(($id:ident, $p:ty)),*) => {
+                match cell_type {
+                    $(CellType::$id => CellBuffer::$id(
+                        self.into_iter().map(|v| v.get::<$p>().unwrap()).collect(),
+                    ),)*
+                }
+            };
+        }
+        Ok(with_ct!(convert))
     }
 
     fn min_max(&self) -> (CellValue, CellValue) {
-        let init = (self.cell_type().max_value(), self.cell_type().min_value());
-        self.into_iter()
-            .fold(init, |(amin, amax), v| (amin.min(v), amax.max(v)))
+        // Fold over the typed values, only wrapping the result in `CellValue`.
+        macro_rules! min_max {
+            ( $(($id:ident, $p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => {
+                        let (min, max) = v.iter().fold((<$p>::MAX, <$p>::MIN), |(lo, hi), x| {
+                            (lo.total_min(*x), hi.total_max(*x))
+                        });
+                        (min.into(), max.into())
+                    })*
+                }
+            };
+        }
+        with_ct!(min_max)
     }
 
     fn to_vec<T: CellEncoding>(self) -> Result<Vec<T>> {
-        let r = self.convert(T::cell_type())?;
+        // Avoid the copy in `convert` when the cell-type already matches.
+        let r = if self.cell_type() == T::cell_type() {
+            self
+        } else {
+            self.convert(T::cell_type())?
+        };
         macro_rules! to_vec {
-            ( $(($id:ident, $_p:ident)),*) => {
+            ( $(($id:ident, $_p:ty)),*) => {
                 match r {
                     $(CellBuffer::$id(b) => Ok(danger::cast(b)),)*
                 }
@@ -190,7 +976,7 @@ impl Debug for CellBuffer {
         use crate::Elided;
         let basename = self.cell_type().to_string();
         macro_rules! render {
-            ( $(($id:ident, $_p:ident)),*) => {{
+            ( $(($id:ident, $_p:ty)),*) => {{
                 f.write_fmt(format_args!("{basename}CellBuffer("))?;
                 match self {
                     $(CellBuffer::$id(b) => f.write_fmt(format_args!("{:?}", Elided(b)))?,)*
@@ -205,54 +991,86 @@ impl Debug for CellBuffer {
 impl<C: CellEncoding> Extend<C> for CellBuffer {
     fn extend<T: IntoIterator<Item = C>>(&mut self, iter: T) {
         macro_rules! render {
-            ( $(($id:ident, $p:ident)),*) => { paste! {
+            ( $(($id:ident, $p:ty)),*) => {
                 match self {
                     $(CellBuffer::$id(b) => {
                         let conv_iter = iter.into_iter().map(|c| {
-                            c.into_cell_value().[<to_ $p>]().unwrap()
+                            <$p as NumCast>::from(c.into_cell_value()).unwrap()
                         });
                         b.extend(conv_iter)
                     },)*
                 }
-            }}
+            }
         }
         with_ct!(render);
     }
 }
 
+/// Extends the buffer with erased values.
+///
+/// If a value cannot be represented in the buffer's current [`CellType`], the buffer is
+/// first widened to the [union][CellType::union] of the two cell-types.
+impl Extend<CellValue> for CellBuffer {
+    fn extend<T: IntoIterator<Item = CellValue>>(&mut self, iter: T) {
+        for v in iter {
+            let ct = self.cell_type().union(v.cell_type());
+            if ct != self.cell_type() {
+                // `unwrap` should be ok as it assumes `CellType::union` is correct.
+                *self = self.convert(ct).unwrap();
+            }
+            macro_rules! push {
+                ( $(($id:ident, $_p:ty)),*) => {
+                    match self {
+                        $(CellBuffer::$id(b) => b.push(v.get().unwrap()),)*
+                    }
+                }
+            }
+            with_ct!(push);
+        }
+    }
+}
+
 impl<C: CellEncoding> FromIterator<C> for CellBuffer {
     fn from_iter<T: IntoIterator<Item = C>>(iter: T) -> Self {
         Self::from_vec(iter.into_iter().collect())
     }
 }
 
+impl Default for CellBuffer {
+    /// Constructs an empty `UInt8` buffer, matching the [`FromIterator`] convention.
+    fn default() -> Self {
+        CellBuffer::with_defaults(0, CellType::UInt8)
+    }
+}
+
+/// Collects values into a buffer of the smallest cell-type that can contain them all, as
+/// with [`CellValue::unify_all`]. An empty iterator produces an empty `UInt8` buffer.
 impl FromIterator<CellValue> for CellBuffer {
     fn from_iter<T: IntoIterator<Item = CellValue>>(iterable: T) -> Self {
-        // TODO: is there a way to avoid this collect?
-        let values = iterable.into_iter().collect::<Vec<CellValue>>();
-        match values.as_slice() {
-            [] => CellBuffer::with_defaults(0, CellType::UInt8),
-            [x, ..] => {
-                let ct: CellType = x.cell_type();
-                macro_rules! conv {
-                    ( $(($id:ident, $_p:ident)),*) => {
-                        match ct {
-                            $(CellType::$id => {
-                                CellBuffer::$id(values.iter().map(|v| v.get().unwrap()).collect())
-                            })*
-                        }
-                    }
-                }
-                with_ct!(conv)
+        let iter = iterable.into_iter();
+        let capacity = iter.size_hint().0;
+        let mut buf = CellBuffer::default();
+        for (i, v) in iter.enumerate() {
+            if i == 0 {
+                buf = CellBuffer::with_capacity(capacity, v.cell_type());
+            }
+            let ct = buf.cell_type().union(v.cell_type());
+            if ct != buf.cell_type() {
+                let capacity = buf.capacity();
+                // `unwrap`s should be ok as they assume `CellType::union` is correct.
+                buf = buf.convert(ct).unwrap();
+                buf.reserve(capacity - buf.len());
             }
+            buf.push(v).unwrap();
         }
+        buf
     }
 }
 
 impl<T: CellEncoding> From<Vec<T>> for CellBuffer {
     fn from(values: Vec<T>) -> Self {
         macro_rules! from {
-            ( $(($id:ident, $_p:ident)),*) => {
+            ( $(($id:ident, $_p:ty)),*) => {
                 match T::cell_type() {
                     $(CellType::$id => Self::$id(danger::cast(values)),)*
                 }
@@ -265,7 +1083,7 @@ impl<T: CellEncoding> From<Vec<T>> for CellBuffer {
 impl<T: CellEncoding> From<&[T]> for CellBuffer {
     fn from(values: &[T]) -> Self {
         macro_rules! from {
-            ( $(($id:ident, $_p:ident)),*) => {
+            ( $(($id:ident, $_p:ty)),*) => {
                 match T::cell_type() {
                     $(CellType::$id => Self::$id(danger::cast(values.to_vec())),)*
                 }
@@ -304,6 +1122,43 @@ impl Iterator for CellBufferIterator<'_> {
     }
 }
 
+/// Mutable reference to a single cell in a [`CellBuffer`], created by [`CellBuffer::cell_mut`].
+///
+/// Dereferences to the cell's current [`CellValue`].
+pub struct CellRefMut<'buf> {
+    buf: &'buf mut CellBuffer,
+    idx: usize,
+    value: CellValue,
+}
+
+impl CellRefMut<'_> {
+    /// Write `value` to the cell.
+    ///
+    /// Unlike [`BufferOps::put`], `value` may be of any cell-type, as long as its value is
+    /// representable in the buffer's cell-type (e.g. `Float64(4.0)` into a `UInt8` buffer).
+    /// Returns `Err(NarrowingError)`, leaving the cell unchanged, otherwise.
+    pub fn set(&mut self, value: CellValue) -> Result<()> {
+        let value = convert_exact(value, self.buf.cell_type())?;
+        self.buf.put(self.idx, value)?;
+        self.value = value;
+        Ok(())
+    }
+}
+
+impl Deref for CellRefMut<'_> {
+    type Target = CellValue;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl Debug for CellRefMut<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.value, f)
+    }
+}
+
 impl<C: CellEncoding> TryFrom<CellBuffer> for Vec<C> {
     type Error = Error;
 
@@ -312,19 +1167,293 @@ impl<C: CellEncoding> TryFrom<CellBuffer> for Vec<C> {
     }
 }
 
+/// Summary statistics of a buffer's values, computed by [`CellBuffer::summary`] or
+/// [`MaskedCellBuffer::summary`][crate::MaskedCellBuffer::summary].
+///
+/// For an empty buffer (or one with no valid cells), `mean`, `std_dev` and `median`
+/// are `NaN`, and `min`/`max` are as returned by [`BufferOps::min_max`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferStats {
+    /// Total number of cells.
+    pub count: usize,
+    /// Number of cells included in the statistics. Same as `count` for a [`CellBuffer`].
+    pub valid_count: usize,
+    /// Minimum value.
+    pub min: CellValue,
+    /// Maximum value.
+    pub max: CellValue,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Population standard deviation, as reported by GDAL.
+    pub std_dev: f64,
+    /// Median, linearly interpolated per [`CellBuffer::percentile`].
+    pub median: f64,
+}
+
+/// Strategy for reconciling buffers of differing lengths in [`align_lengths`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LengthPolicy {
+    /// Truncate all buffers to the length of the shortest.
+    TruncateToShortest,
+    /// Extend all buffers to the length of the longest, filling new cells with `fill`.
+    PadToLongest { fill: CellValue },
+}
+
+/// Bring `buffers` to a common length according to `policy`.
+///
+/// When padding, a buffer is first widened to the [union][CellType::union] of its
+/// cell-type and that of `fill` if `fill` would not otherwise fit.
+pub fn align_lengths(buffers: &mut [CellBuffer], policy: LengthPolicy) {
+    let lens = buffers.iter().map(|b| b.len());
+    match policy {
+        LengthPolicy::TruncateToShortest => {
+            let len = lens.min().unwrap_or_default();
+            for b in buffers {
+                let zero = b.cell_type().zero();
+                b.resize(len, zero).unwrap();
+            }
+        }
+        LengthPolicy::PadToLongest { fill } => {
+            let len = lens.max().unwrap_or_default();
+            for b in buffers.iter_mut().filter(|b| b.len() < len) {
+                let ct = b.cell_type().union(fill.cell_type());
+                if ct != b.cell_type() {
+                    *b = b.convert(ct).unwrap();
+                }
+                b.resize(len, fill).unwrap();
+            }
+        }
+    }
+}
+
+/// Total ordering over primitives, consistent with the [`Ord`] implementation of [`CellValue`].
+pub(crate) trait TotalOrd: Copy {
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    fn total_min(self, other: Self) -> Self {
+        match self.total_cmp(&other) {
+            Ordering::Greater => other,
+            _ => self,
+        }
+    }
+
+    fn total_max(self, other: Self) -> Self {
+        match self.total_cmp(&other) {
+            Ordering::Less => other,
+            _ => self,
+        }
+    }
+}
+
+macro_rules! total_ord {
+    ($($p:ty),*) => { $(
+        impl TotalOrd for $p {
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                <$p>::total_cmp(self, other)
+            }
+        }
+    )* };
+}
+total_ord!(f32, f64);
+#[cfg(feature = "f16")]
+total_ord!(f16);
+
+macro_rules! ord {
+    ($($p:ty),*) => { $(
+        impl TotalOrd for $p {
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                Ord::cmp(self, other)
+            }
+        }
+    )* };
+}
+ord!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Convert `value` to `cell_type` if its value (rather than its cell-type) is representable,
+/// accepting e.g. `Int32(100)` as an `Int16`.
+///
+/// Returns `Err(NarrowingError)` otherwise.
+fn convert_exact(value: CellValue, cell_type: CellType) -> Result<CellValue> {
+    match value.convert_clamped(cell_type) {
+        c if c == value => Ok(c),
+        _ => Err(Error::NarrowingError { src: value.cell_type(), dst: cell_type }),
+    }
+}
+
+/// Count the occurrences of each distinct value in `values`.
+pub(crate) fn value_counts<I>(values: I) -> BTreeMap<CellValue, usize>
+where
+    I: IntoIterator<Item = CellValue>,
+{
+    values.into_iter().fold(BTreeMap::new(), |mut acc, v| {
+        *acc.entry(v).or_insert(0) += 1;
+        acc
+    })
+}
+
+/// Compute the `-p log₂ p` entropy term for each entry in `counts`.
+pub(crate) fn entropy_per_class(counts: &BTreeMap<CellValue, usize>) -> BTreeMap<CellValue, f64> {
+    let total = counts.values().sum::<usize>() as f64;
+    counts
+        .iter()
+        .map(|(v, c)| {
+            let p = *c as f64 / total;
+            (*v, -p * p.log2())
+        })
+        .collect()
+}
+
+/// Find the index of the first value in `values` that is the most `want` per `cmp`.
+pub(crate) fn arg_by<T, I, F>(values: I, want: Ordering, cmp: F) -> Option<usize>
+where
+    I: IntoIterator<Item = (usize, T)>,
+    F: Fn(&T, &T) -> Ordering,
+{
+    values
+        .into_iter()
+        .fold(None, |best, (i, x)| match best {
+            Some((j, b)) if cmp(&x, &b) != want => Some((j, b)),
+            _ => Some((i, x)),
+        })
+        .map(|(i, _)| i)
+}
+
+/// Compute [`BufferStats`] over `values`, reporting `count` total cells.
+pub(crate) fn summary(values: &CellBuffer, count: usize) -> BufferStats {
+    let (min, max) = values.min_max();
+    // Welford's algorithm, for numerical stability in a single pass.
+    let (n, mean, m2) = values
+        .into_iter()
+        .fold((0usize, 0.0, 0.0), |(n, mean, m2), v| {
+            let x = v.to_f64().unwrap();
+            let n = n + 1;
+            let delta = x - mean;
+            let mean = mean + delta / n as f64;
+            (n, mean, m2 + delta * (x - mean))
+        });
+    let (mean, std_dev) = if n == 0 {
+        (f64::NAN, f64::NAN)
+    } else {
+        (mean, (m2 / n as f64).sqrt())
+    };
+    BufferStats {
+        count,
+        valid_count: n,
+        min,
+        max,
+        mean,
+        std_dev,
+        median: values.percentile(50.0).to_f64().unwrap(),
+    }
+}
+
+/// Compute the sum of products of paired values in `ct`, the [union][CellType::union] of
+/// the cell-types of each side.
+///
+/// Integral sums are computed exactly with checked arithmetic, falling back to `Float64` on
+/// overflow of `ct`. Floating-point sums are accumulated in `f64` and then narrowed to `ct`.
+pub(crate) fn dot<I>(ct: CellType, pairs: I) -> CellValue
+where
+    I: IntoIterator<Item = (CellValue, CellValue)>,
+{
+    let (exact, sum) = pairs.into_iter().fold(
+        (ct.is_integral().then(|| ct.zero()), 0.0),
+        |(exact, sum), (a, b)| {
+            let exact = exact.and_then(|acc| acc.checked_add(a.checked_mul(b)?));
+            (exact, sum + a.to_f64().unwrap() * b.to_f64().unwrap())
+        },
+    );
+    match (exact, ct) {
+        (Some(v), _) => v,
+        (None, CellType::Float32) => CellValue::Float32(sum as f32),
+        #[cfg(feature = "f16")]
+        (None, CellType::Float16) => CellValue::Float16(f16::from_f64(sum)),
+        _ => CellValue::Float64(sum),
+    }
+}
+
+/// Compute the Pearson correlation coefficient over paired values.
+pub(crate) fn pearson<I>(pairs: I) -> f64
+where
+    I: IntoIterator<Item = (CellValue, CellValue)>,
+{
+    let pairs: Vec<(f64, f64)> = pairs
+        .into_iter()
+        .map(|(x, y)| (x.to_f64().unwrap(), y.to_f64().unwrap()))
+        .collect();
+    let n = pairs.len() as f64;
+    let (x_sum, y_sum) = pairs
+        .iter()
+        .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+    let (x_mean, y_mean) = (x_sum / n, y_sum / n);
+    let (cov, x_var, y_var) = pairs.iter().fold((0.0, 0.0, 0.0), |(c, vx, vy), (x, y)| {
+        let (dx, dy) = (x - x_mean, y - y_mean);
+        (c + dx * dy, vx + dx * dx, vy + dy * dy)
+    });
+    cov / (x_var * y_var).sqrt()
+}
+
 mod ops {
     use std::cmp::Ordering;
+    use std::hash::{Hash, Hasher};
     use std::ops::{Add, Div, Mul, Neg, Sub};
 
-    use crate::{BufferOps, CellBuffer, CellValue};
+    use num_traits::AsPrimitive;
+    use paste::paste;
+
+    use crate::error::{Error, Result};
+    use crate::{with_ct, BufferOps, CellBuffer, CellEncoding, CellValue};
+
+    /// Fast path for binary operations between non-empty buffers of the same cell-type,
+    /// operating directly on the typed `Vec`s rather than through [`CellValue`].
+    ///
+    /// [`CellValue`] arithmetic promotes both operands to `f64`, so doing the same here
+    /// gives identical results. Returns `None` when the slow path must be taken.
+    fn same_type_bin_op<F>(lhs: &CellBuffer, rhs: &CellBuffer, op: F) -> Option<CellBuffer>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        // Empty results are collected as `UInt8` by the slow path.
+        if lhs.is_empty() || rhs.is_empty() {
+            return None;
+        }
+        macro_rules! same_type {
+            ( $(($id:ident, $p:ty)),*) => {
+                match (lhs, rhs) {
+                    $((CellBuffer::$id(l), CellBuffer::$id(r)) => Some(CellBuffer::Float64(
+                        l.iter()
+                            .zip(r)
+                            .map(|(a, b)| op(AsPrimitive::<f64>::as_(*a), AsPrimitive::<f64>::as_(*b)))
+                            .collect(),
+                    )),)*
+                    _ => None,
+                }
+            };
+        }
+        with_ct!(same_type)
+    }
 
     macro_rules! cb_bin_op {
-        ($trt:ident, $mth:ident, $op:tt) => {
+        ($trt:ident, $mth:ident, $op:tt) => { paste! {
+            impl CellBuffer {
+                #[doc = concat!("Element-wise `", stringify!($op), "` of `self` and `rhs`.")]
+                ///
+                /// Returns `Err(LengthMismatch)` if the buffers differ in length.
+                pub fn [<try_ $mth>](&self, rhs: &CellBuffer) -> Result<CellBuffer> {
+                    if self.len() != rhs.len() {
+                        return Err(Error::LengthMismatch { lhs: self.len(), rhs: rhs.len() });
+                    }
+                    Ok(same_type_bin_op(self, rhs, |l, r| l $op r).unwrap_or_else(|| {
+                        self.into_iter().zip(rhs.into_iter()).map(|(l, r)| l $op r).collect()
+                    }))
+                }
+            }
             // Both borrows.
+            #[doc = concat!("# Panics\nWill panic if the operands are not the same length. See [`CellBuffer::try_", stringify!($mth), "`].")]
             impl $trt for &CellBuffer {
                 type Output = CellBuffer;
                 fn $mth(self, rhs: Self) -> Self::Output {
-                    self.into_iter().zip(rhs.into_iter()).map(|(l, r)| l $op r).collect()
+                    self.[<try_ $mth>](rhs).unwrap_or_else(|e| panic!("{e}"))
                 }
             }
             // Both owned/consumed
@@ -342,7 +1471,6 @@ mod ops {
                 }
             }
             // RHS scalar
-            // TODO: figure out how to implement LHS scalar, avoiding orphan rule.
             impl <R> $trt<R> for CellBuffer where R: Into<CellValue> {
                 type Output = CellBuffer;
                 fn $mth(self, rhs: R) -> Self::Output {
@@ -350,13 +1478,41 @@ mod ops {
                     self.into_iter().map(|l | l $op r).collect()
                 }
             }
-        }
+        }}
     }
     cb_bin_op!(Add, add, +);
     cb_bin_op!(Sub, sub, -);
     cb_bin_op!(Mul, mul, *);
     cb_bin_op!(Div, div, /);
 
+    // LHS scalar. The orphan rule requires a concrete impl for each primitive.
+    macro_rules! cb_lhs_scalar_op {
+        ($trt:ident, $mth:ident, $op:tt, $($p:ty),*) => { $(
+            impl $trt<&CellBuffer> for $p {
+                type Output = CellBuffer;
+                fn $mth(self, rhs: &CellBuffer) -> Self::Output {
+                    let l: CellValue = self.into();
+                    rhs.into_iter().map(|r| l $op r).collect()
+                }
+            }
+            impl $trt<CellBuffer> for $p {
+                type Output = CellBuffer;
+                fn $mth(self, rhs: CellBuffer) -> Self::Output {
+                    $trt::$mth(self, &rhs)
+                }
+            }
+        )* };
+    }
+    macro_rules! cb_lhs_scalar {
+        ( $(($_id:ident, $p:ty)),*) => {
+            cb_lhs_scalar_op!(Add, add, +, $($p),*);
+            cb_lhs_scalar_op!(Sub, sub, -, $($p),*);
+            cb_lhs_scalar_op!(Mul, mul, *, $($p),*);
+            cb_lhs_scalar_op!(Div, div, /, $($p),*);
+        };
+    }
+    with_ct!(cb_lhs_scalar);
+
     impl Neg for &CellBuffer {
         type Output = CellBuffer;
         fn neg(self) -> Self::Output {
@@ -378,6 +1534,43 @@ mod ops {
 
     impl Eq for CellBuffer {}
 
+    /// Equal iff the cell-types match and all elements are equal, comparing
+    /// floating-point cells with `total_cmp`.
+    impl<T: CellEncoding> PartialEq<&[T]> for CellBuffer {
+        fn eq(&self, other: &&[T]) -> bool {
+            self.cell_type() == T::cell_type()
+                && self.len() == other.len()
+                && self
+                    .into_iter()
+                    .zip(other.iter())
+                    .all(|(l, r)| l == r.into_cell_value())
+        }
+    }
+
+    /// See [`PartialEq<&[T]>`](#impl-PartialEq%3C%26%5BT%5D%3E-for-CellBuffer).
+    impl<T: CellEncoding> PartialEq<Vec<T>> for CellBuffer {
+        fn eq(&self, other: &Vec<T>) -> bool {
+            *self == other.as_slice()
+        }
+    }
+
+    /// Hashes the cell-type and the bit pattern of each cell, consistent with the
+    /// `total_cmp`-based equality of floating-point buffers.
+    impl Hash for CellBuffer {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.cell_type().hash(state);
+            state.write_usize(self.len());
+            macro_rules! hash {
+                ( $(($id:ident, $_p:ty)),*) => {
+                    match self {
+                        $(CellBuffer::$id(v) => v.iter().for_each(|c| state.write(&c.to_le_bytes())),)*
+                    }
+                };
+            }
+            with_ct!(hash)
+        }
+    }
+
     impl PartialOrd for CellBuffer {
         fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
             Some(self.cmp(other))
@@ -428,6 +1621,8 @@ mod ops {
                 (CellBuffer::Int16(lhs), CellBuffer::Int16(rhs)) => Ord::cmp(&lhs, &rhs),
                 (CellBuffer::Int32(lhs), CellBuffer::Int32(rhs)) => Ord::cmp(&lhs, &rhs),
                 (CellBuffer::Int64(lhs), CellBuffer::Int64(rhs)) => Ord::cmp(&lhs, &rhs),
+                #[cfg(feature = "f16")]
+                (CellBuffer::Float16(l), CellBuffer::Float16(r)) => total_cmp!(l, r),
                 (CellBuffer::Float32(l), CellBuffer::Float32(r)) => total_cmp!(l, r),
                 (CellBuffer::Float64(l), CellBuffer::Float64(r)) => total_cmp!(l, r),
                 _ => unreachable!("{self:?} <> {other:?}"),
@@ -436,6 +1631,8 @@ mod ops {
     }
 }
 
+// Note: `bytemuck` can't replace these casts, as `T` and `P` are only known to
+// be `CellEncoding`, not `Pod`.
 mod danger {
     use crate::CellEncoding;
 
@@ -448,11 +1645,26 @@ mod danger {
             Vec::from_raw_parts(v.as_mut_ptr() as *mut P, v.len(), v.capacity())
         }
     }
+
+    #[inline]
+    pub(crate) fn cast_slice<T: CellEncoding, P: CellEncoding>(slice: &[T]) -> &[P] {
+        assert_eq!(T::cell_type(), P::cell_type());
+        unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const P, slice.len()) }
+    }
+
+    #[inline]
+    pub(crate) fn cast_slice_mut<T: CellEncoding, P: CellEncoding>(slice: &mut [T]) -> &mut [P] {
+        assert_eq!(T::cell_type(), P::cell_type());
+        unsafe { std::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut P, slice.len()) }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{with_ct, BufferOps, CellBuffer, CellType, CellValue};
+    use crate::error::Error;
+    use crate::{with_ct, BufferOps, CellBuffer, CellType, CellValue, LengthPolicy};
+    #[cfg(feature = "f16")]
+    use half::f16;
 
     fn bigger(start: CellType) -> impl Iterator<Item = CellType> {
         CellType::iter().filter(move |ct| start.can_fit_into(*ct))
@@ -461,7 +1673,7 @@ mod tests {
     #[test]
     fn defaults() {
         macro_rules! test {
-            ($( ($id:ident, $p:ident) ),*) => {
+            ($( ($id:ident, $p:ty) ),*) => {
                 $({
                     let cv = CellBuffer::with_defaults(3, CellType::$id);
                     assert_eq!(cv.len(), 3);
@@ -469,13 +1681,16 @@ mod tests {
                 })*};
         }
         with_ct!(test);
+        let buf = CellBuffer::default();
+        assert!(buf.is_empty());
+        assert_eq!(buf.cell_type(), CellType::UInt8);
     }
 
     #[test]
     fn put_get() {
         use num_traits::One;
         macro_rules! test {
-            ($( ($id:ident, $p:ident) ),*) => {
+            ($( ($id:ident, $p:ty) ),*) => {
                 $({
                     let mut cv = CellBuffer::fill(3, <$p>::default().into());
                     let one = CellValue::new(<$p>::one());
@@ -497,10 +1712,51 @@ mod tests {
         assert_eq!(buf.get(3), 1.into());
     }
 
+    #[test]
+    fn capacity() {
+        let mut buf = CellBuffer::with_capacity(10, CellType::Float32);
+        assert!(buf.is_empty());
+        assert_eq!(buf.cell_type(), CellType::Float32);
+        assert!(buf.capacity() >= 10);
+        buf.reserve(20);
+        assert!(buf.capacity() >= 20);
+
+        let buf: CellBuffer = (0..10u8).map(CellValue::new).collect();
+        assert!(buf.capacity() >= 10);
+        assert_eq!(buf, CellBuffer::fill_via(10, |i| i as u8));
+    }
+
+    #[test]
+    fn push_resize() {
+        let mut buf = CellBuffer::fill(2, 1i16.into());
+        buf.push(2u8.into()).unwrap();
+        assert_eq!(buf, CellBuffer::from_vec(vec![1i16, 1, 2]));
+        assert!(buf.push(2.5.into()).is_err());
+        assert_eq!(buf.len(), 3);
+
+        // Grow
+        buf.resize(5, (-1i8).into()).unwrap();
+        assert_eq!(buf, CellBuffer::from_vec(vec![1i16, 1, 2, -1, -1]));
+        // Shrink
+        buf.resize(2, 0i16.into()).unwrap();
+        assert_eq!(buf, CellBuffer::from_vec(vec![1i16, 1]));
+        assert!(buf.resize(4, 0i32.into()).is_err());
+    }
+
+    #[test]
+    fn extend_values() {
+        let mut buf = CellBuffer::fill(2, 1u8.into());
+        buf.extend([CellValue::UInt8(2), CellValue::UInt16(3)]);
+        assert_eq!(buf, CellBuffer::from_vec(vec![1u16, 1, 2, 3]));
+        buf.extend([CellValue::Float32(0.5)]);
+        assert_eq!(buf.cell_type(), CellType::Float32);
+        assert_eq!(buf.get(4), 0.5.into());
+    }
+
     #[test]
     fn to_vec() {
         macro_rules! test {
-            ($( ($id:ident, $p:ident) ),*) => {
+            ($( ($id:ident, $p:ty) ),*) => {
                 $({
                     let v = vec![<$p>::default(); 3];
                     let buf = CellBuffer::from_vec(v.clone());
@@ -525,6 +1781,52 @@ mod tests {
         assert_eq!(max, CellValue::UInt8(200));
     }
 
+    #[test]
+    fn min_max_matches_cell_value_fold() {
+        fn fold(buf: &CellBuffer) -> (CellValue, CellValue) {
+            let init = (buf.cell_type().max_value(), buf.cell_type().min_value());
+            buf.into_iter()
+                .fold(init, |(amin, amax), v| (amin.min(v), amax.max(v)))
+        }
+        use num_traits::ToPrimitive;
+        let bits = |(l, r): (CellValue, CellValue)| {
+            (l.to_f64().unwrap().to_bits(), r.to_f64().unwrap().to_bits())
+        };
+        for ct in CellType::iter() {
+            let buf = CellBuffer::fill_via(50, |i| (i * 37 % 101) as u8).convert(ct);
+            if let Ok(buf) = buf {
+                assert_eq!(buf.min_max(), fold(&buf), "{ct}");
+            }
+            let empty = CellBuffer::with_defaults(0, ct);
+            assert_eq!(empty.min_max(), fold(&empty), "{ct}");
+        }
+        let buf = CellBuffer::from_vec(vec![0.0, -0.0, f64::NAN, -f64::NAN, 1.0]);
+        assert_eq!(bits(buf.min_max()), bits(fold(&buf)));
+        let buf = CellBuffer::from_vec(vec![0.0f32, -0.0, f32::NEG_INFINITY]);
+        assert_eq!(bits(buf.min_max()), bits(fold(&buf)));
+    }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn float16() {
+        let v: Vec<f16> = [-1.5, 3.0, 2000.0, -5.25].map(f16::from_f32).to_vec();
+        let buf = CellBuffer::from_vec(v.clone());
+        assert_eq!(buf.cell_type(), CellType::Float16);
+
+        let (min, max) = buf.min_max();
+        assert_eq!(min, CellValue::Float16(f16::from_f32(-5.25)));
+        assert_eq!(max, CellValue::Float16(f16::from_f32(2000.0)));
+
+        let r = buf.convert(CellType::Float32).unwrap();
+        assert_eq!(r, CellBuffer::from_vec(vec![-1.5f32, 3.0, 2000.0, -5.25]));
+        assert!(buf.convert(CellType::UInt8).is_err());
+        let r = CellBuffer::from_vec(vec![1u8, 255]).convert(CellType::Float16);
+        assert_eq!(r.unwrap().to_vec::<f16>().unwrap()[1], f16::from_f32(255.0));
+
+        assert_eq!(buf.clone().to_vec::<f16>().unwrap(), v);
+        assert_eq!(buf.to_vec::<f64>().unwrap(), vec![-1.5, 3.0, 2000.0, -5.25]);
+    }
+
     #[test]
     fn from_others() {
         let v = vec![
@@ -555,6 +1857,704 @@ mod tests {
         assert_eq!(b.get(2), CellValue::Float32(55.5));
     }
 
+    #[test]
+    fn from_mixed_values() {
+        let v = vec![
+            CellValue::UInt8(200),
+            CellValue::Float32(1.5),
+            CellValue::UInt8(7),
+        ];
+        let b: CellBuffer = v.into_iter().collect();
+        assert_eq!(b, CellBuffer::from_vec(vec![200.0f32, 1.5, 7.0]));
+
+        let v = vec![CellValue::UInt8(200), CellValue::Int8(-1)];
+        let b: CellBuffer = v.into_iter().collect();
+        assert_eq!(b, CellBuffer::from_vec(vec![200i16, -1]));
+
+        let b: CellBuffer = std::iter::empty::<CellValue>().collect();
+        assert_eq!(b, CellBuffer::default());
+    }
+
+    #[test]
+    fn collect_heterogeneous() {
+        let b: CellBuffer = [CellValue::UInt8(1), CellValue::Float32(2.0)]
+            .into_iter()
+            .collect();
+        assert_eq!(b.cell_type(), CellType::Float32);
+        assert_eq!(b, CellBuffer::from_vec(vec![1.0f32, 2.0]));
+
+        // Mapping to a type determined by value widens earlier cells.
+        let src = CellBuffer::from_vec(vec![1u8, 2, 3]);
+        let b: CellBuffer = src
+            .into_iter()
+            .map(|v| {
+                if v > 2.into() {
+                    CellValue::Int64(-1)
+                } else {
+                    v
+                }
+            })
+            .collect();
+        assert_eq!(b, CellBuffer::from_vec(vec![1i64, 2, -1]));
+    }
+
+    #[test]
+    fn validate() {
+        let mut buf = CellBuffer::fill_via(4, |i| i as f32);
+        assert!(buf.validate().is_ok());
+        buf.put(1, f32::NAN.into()).unwrap();
+        assert!(buf.validate().is_ok());
+        buf.as_mut_slice::<f32>().unwrap()[2] = f32::from_bits(0x7f80_0001);
+        assert!(buf.validate().is_err());
+
+        let mut buf = CellBuffer::fill(4, f64::NAN.into());
+        assert!(buf.validate().is_ok());
+        buf.as_mut_slice::<f64>().unwrap()[3] = f64::from_bits(0x7ff0_0000_0000_0001);
+        assert!(buf.validate().is_err());
+
+        #[cfg(feature = "f16")]
+        {
+            let mut buf = CellBuffer::fill(4, f16::NAN.into());
+            assert!(buf.validate().is_ok());
+            buf.as_mut_slice::<f16>().unwrap()[0] = f16::from_bits(0x7c01);
+            assert!(buf.validate().is_err());
+        }
+
+        assert!(CellBuffer::fill(4, 1u8.into()).validate().is_ok());
+    }
+
+    #[test]
+    fn bytes() {
+        for ct in CellType::iter() {
+            let mut buf = CellBuffer::with_defaults(5, ct);
+            buf.put(1, ct.one()).unwrap();
+            buf.put(2, ct.min_value()).unwrap();
+            buf.put(3, ct.max_value()).unwrap();
+            let le = buf.to_le_bytes();
+            let be = buf.to_be_bytes();
+            assert_eq!(le.len(), 5 * ct.size_of());
+            assert_eq!(CellBuffer::from_le_bytes(ct, &le).unwrap(), buf, "{ct}");
+            assert_eq!(CellBuffer::from_be_bytes(ct, &be).unwrap(), buf, "{ct}");
+        }
+
+        let buf = CellBuffer::from_vec(vec![0x0102u16, 0x0304]);
+        assert_eq!(buf.to_le_bytes(), [0x02, 0x01, 0x04, 0x03]);
+        assert_eq!(buf.to_be_bytes(), [0x01, 0x02, 0x03, 0x04]);
+
+        assert!(CellBuffer::from_le_bytes(CellType::Int32, &[0u8; 7]).is_err());
+        assert!(CellBuffer::from_be_bytes(CellType::Float64, &[0u8; 4]).is_err());
+        assert!(CellBuffer::from_le_bytes(CellType::UInt16, &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn nan_to_num() {
+        let buf = CellBuffer::from_vec(vec![f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 2.0]);
+        assert_eq!(
+            buf.nan_to_num(0.0, 1e9, -1e9),
+            CellBuffer::from_vec(vec![0.0, 1e9, -1e9, 2.0])
+        );
+        let buf = CellBuffer::from_vec(vec![f32::NAN, 1.0]);
+        assert_eq!(
+            buf.nan_to_num(0.0, 1e9, -1e9),
+            CellBuffer::from_vec(vec![0.0f32, 1.0])
+        );
+        let buf = CellBuffer::from_vec(vec![1u8, 2]);
+        assert_eq!(buf.nan_to_num(0.0, 1e9, -1e9), buf);
+        let buf = CellBuffer::with_defaults(0, CellType::Float32);
+        assert_eq!(buf.nan_to_num(0.0, 1e9, -1e9), buf);
+    }
+
+    #[test]
+    fn sort() {
+        let mut buf = CellBuffer::from_vec(vec![3i16, -1, 2, 0]);
+        buf.sort();
+        assert_eq!(buf, CellBuffer::from_vec(vec![-1i16, 0, 2, 3]));
+
+        let buf = CellBuffer::from_vec(vec![2.0, f64::NAN, -1.0, f64::NEG_INFINITY]);
+        let sorted = buf.sorted();
+        // Positive `NaN` sorts last.
+        assert_eq!(
+            sorted,
+            CellBuffer::from_vec(vec![f64::NEG_INFINITY, -1.0, 2.0, f64::NAN])
+        );
+        assert_eq!(sorted.cell_type(), CellType::Float64);
+    }
+
+    #[test]
+    fn zip_with() {
+        let a = CellBuffer::from_vec(vec![1u8, 200, 3]);
+        let b = CellBuffer::from_vec(vec![-4i16, 5, 300]);
+        let max = |l: CellValue, r: CellValue| {
+            let (l, r) = l.unify(&r);
+            l.max(r)
+        };
+        let r = a.zip_with(&b, max).unwrap();
+        assert_eq!(r, CellBuffer::from_vec(vec![1i16, 200, 300]));
+        assert!(a.zip_with(&b.slice(1..), max).is_err());
+    }
+
+    #[test]
+    fn predicates() {
+        let buf = CellBuffer::from_vec(vec![1.5, 7.0, 3.0, 9.5, 6.0]);
+        let above = |v: CellValue| v > 5.into();
+        assert_eq!(buf.count_where(above), 3);
+        assert_eq!(buf.position(above), Some(1));
+        assert_eq!(buf.find(above), Some(7.0.into()));
+        assert_eq!(buf.position(|v| v > 10.into()), None);
+        assert_eq!(buf.find(|v| v < 0.into()), None);
+    }
+
+    #[test]
+    fn unique() {
+        let buf = CellBuffer::from_vec(vec![1u8, 1, 2, 2, 3]);
+        assert_eq!(buf.unique(), CellBuffer::from_vec(vec![1u8, 2, 3]));
+        let buf = CellBuffer::from_vec(vec![3i32, 1, 3, 2, 1]);
+        assert_eq!(buf.unique(), CellBuffer::from_vec(vec![3i32, 1, 2]));
+        let buf = CellBuffer::from_vec(vec![f64::NAN, 0.0, f64::NAN, -0.0]);
+        assert_eq!(
+            buf.unique(),
+            CellBuffer::from_vec(vec![f64::NAN, 0.0, -0.0])
+        );
+    }
+
+    #[test]
+    fn dedup() {
+        let mut buf = CellBuffer::from_vec(vec![1u8, 1, 2, 2, 3, 1]);
+        buf.dedup();
+        assert_eq!(buf, CellBuffer::from_vec(vec![1u8, 2, 3, 1]));
+        let mut buf = CellBuffer::from_vec(vec![0.5f32, 0.5, f32::NAN, f32::NAN]);
+        buf.dedup();
+        assert_eq!(buf, CellBuffer::from_vec(vec![0.5f32, f32::NAN]));
+    }
+
+    #[test]
+    fn argmin_argmax() {
+        let buf = CellBuffer::from_vec(vec![3i32, -1, 7, 12, -1, 5]);
+        assert_eq!(buf.argmin(), Some(1));
+        assert_eq!(buf.argmax(), Some(3));
+        let buf = CellBuffer::from_vec(vec![2u8, 2, 2]);
+        assert_eq!((buf.argmin(), buf.argmax()), (Some(0), Some(0)));
+        let buf = CellBuffer::from_vec(vec![1.0, f64::NAN, -f64::NAN, 4.0]);
+        assert_eq!((buf.argmin(), buf.argmax()), (Some(2), Some(1)));
+        assert_eq!(CellBuffer::default().argmin(), None);
+        assert_eq!(CellBuffer::default().argmax(), None);
+    }
+
+    #[test]
+    fn cell_mut() {
+        let mut buf = CellBuffer::from_vec(vec![1i16, 2, 3]);
+        {
+            let mut cell = buf.cell_mut(2);
+            assert_eq!(*cell, CellValue::Int16(3));
+            cell.set(*cell * 2).unwrap();
+            assert_eq!(*cell, CellValue::Int16(6));
+            assert!(matches!(
+                cell.set(0.5.into()),
+                Err(Error::NarrowingError { .. })
+            ));
+            assert!(matches!(
+                cell.set(40000.into()),
+                Err(Error::NarrowingError { .. })
+            ));
+            assert_eq!(*cell, CellValue::Int16(6));
+        }
+        assert_eq!(buf, CellBuffer::from_vec(vec![1i16, 2, 6]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn cell_mut_out_of_bounds() {
+        CellBuffer::from_vec(vec![1u8]).cell_mut(1);
+    }
+
+    #[test]
+    fn extend_from_buffer() {
+        let mut buf = CellBuffer::from_vec(vec![0.5f64]);
+        buf.extend_from_buffer(&CellBuffer::from_vec(vec![1u8, 255]))
+            .unwrap();
+        assert_eq!(buf, CellBuffer::from_vec(vec![0.5, 1.0, 255.0]));
+        buf.extend_from_buffer(&CellBuffer::default()).unwrap();
+        assert_eq!(buf.len(), 3);
+        let mut empty = CellBuffer::default();
+        empty
+            .extend_from_buffer(&CellBuffer::from_vec(vec![7u8]))
+            .unwrap();
+        assert_eq!(empty.convert(CellType::Float64).unwrap(), vec![7.0]);
+        let mut empty = CellBuffer::default().convert(CellType::Int32).unwrap();
+        assert_eq!(empty.cell_type(), CellType::Int32);
+        empty
+            .extend_from_buffer(&CellBuffer::from_vec(vec![-1i16]))
+            .unwrap();
+        assert_eq!(empty, vec![-1i32]);
+
+        let mut buf = CellBuffer::from_vec(vec![1u8]);
+        let r = buf.extend_from_buffer(&CellBuffer::from_vec(vec![0.5f64]));
+        assert!(matches!(r, Err(Error::NarrowingError { .. })));
+        assert_eq!(buf, CellBuffer::from_vec(vec![1u8]));
+        buf.append(&CellBuffer::from_vec(vec![0.5f64])).unwrap();
+        assert_eq!(buf, CellBuffer::from_vec(vec![1.0, 0.5]));
+    }
+
+    #[test]
+    fn swap() {
+        let orig = CellBuffer::from_vec(vec![1.5f32, 2.5, 3.5]);
+        let mut buf = orig.clone();
+        buf.swap(0, 2);
+        assert_eq!(buf, CellBuffer::from_vec(vec![3.5f32, 2.5, 1.5]));
+        buf.swap(2, 0);
+        assert_eq!(buf, orig);
+        buf.swap(1, 1);
+        assert_eq!(buf, orig);
+    }
+
+    #[test]
+    fn summary() {
+        let buf = CellBuffer::from_vec(vec![2i16, 4, 4, 4, 5, 5, 7, 9]);
+        let s = buf.summary();
+        assert_eq!((s.count, s.valid_count), (8, 8));
+        assert_eq!((s.min, s.max), (CellValue::Int16(2), CellValue::Int16(9)));
+        assert_eq!(s.mean, 5.0);
+        assert_eq!(s.std_dev, 2.0);
+        assert_eq!(s.median, 4.5);
+
+        let s = CellBuffer::from_vec(vec![1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0]).summary();
+        assert_eq!(s.mean, 1e9 + 10.0);
+        assert!((s.std_dev - 22.5f64.sqrt()).abs() < 1e-6);
+
+        let s = CellBuffer::default().summary();
+        assert_eq!((s.count, s.valid_count), (0, 0));
+        assert!(s.mean.is_nan() && s.std_dev.is_nan() && s.median.is_nan());
+    }
+
+    #[test]
+    fn percentile() {
+        use num_traits::ToPrimitive;
+        let buf = CellBuffer::from_vec(vec![4u8, 2, 1, 3]);
+        assert_eq!(buf.percentile(50.0), CellValue::Float64(2.5));
+        assert_eq!(buf.percentile(0.0), CellValue::Float64(1.0));
+        assert_eq!(buf.percentile(100.0), CellValue::Float64(4.0));
+        assert_eq!(buf.percentile(25.0), CellValue::Float64(1.75));
+
+        let buf = CellBuffer::from_vec(vec![1.0f32, f32::NAN, 2.0]);
+        assert_eq!(buf.percentile(0.0), CellValue::Float64(1.0));
+        assert!(buf.percentile(100.0).to_f64().unwrap().is_nan());
+
+        let empty = CellBuffer::with_defaults(0, CellType::UInt8);
+        assert!(empty.percentile(50.0).to_f64().unwrap().is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn percentile_out_of_range() {
+        let _ = CellBuffer::from_vec(vec![1u8]).percentile(101.0);
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::HashSet;
+        let set: HashSet<CellBuffer> = [
+            CellBuffer::from_vec(vec![1u8, 2]),
+            CellBuffer::from_vec(vec![1u8, 2]),
+            CellBuffer::from_vec(vec![1u16, 2]),
+            CellBuffer::from_vec(vec![f64::NAN, 0.0]),
+            CellBuffer::from_vec(vec![f64::NAN, 0.0]),
+            CellBuffer::from_vec(vec![f64::NAN, -0.0]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 4);
+    }
+
+    #[test]
+    fn from_raw_parts() {
+        let v = std::mem::ManuallyDrop::new(vec![1.5f64, -2.0, 3.25]);
+        let (ptr, len, cap) = (v.as_ptr() as *mut u8, v.len(), v.capacity());
+        let buf = unsafe { CellBuffer::from_raw_parts(ptr, len, cap, CellType::Float64) };
+        assert_eq!(buf, CellBuffer::from_vec(vec![1.5f64, -2.0, 3.25]));
+        assert_eq!(buf.capacity(), cap);
+    }
+
+    #[test]
+    fn align_lengths() {
+        let bufs = [
+            CellBuffer::from_vec(vec![1u8, 2, 3]),
+            CellBuffer::from_vec(vec![1.5f32, 2.5, 3.5, 4.5, 5.5]),
+        ];
+
+        let mut r = bufs.clone();
+        super::align_lengths(&mut r, LengthPolicy::TruncateToShortest);
+        assert_eq!(r[0], bufs[0]);
+        assert_eq!(r[1], CellBuffer::from_vec(vec![1.5f32, 2.5, 3.5]));
+
+        let mut r = bufs.clone();
+        super::align_lengths(&mut r, LengthPolicy::PadToLongest { fill: 0u8.into() });
+        assert_eq!(r[0], CellBuffer::from_vec(vec![1u8, 2, 3, 0, 0]));
+        assert_eq!(r[1], bufs[1]);
+
+        // Fill value requiring widening.
+        let mut r = bufs.clone();
+        let fill = CellValue::Int16(-1);
+        super::align_lengths(&mut r, LengthPolicy::PadToLongest { fill });
+        assert_eq!(r[0], CellBuffer::from_vec(vec![1i16, 2, 3, -1, -1]));
+    }
+
+    #[test]
+    fn reinterpret() {
+        let buf = CellBuffer::from_vec(vec![1.0f32, -2.5]);
+        let bits = buf.reinterpret(CellType::UInt32).unwrap();
+        assert_eq!(
+            bits,
+            CellBuffer::from_vec(vec![0x3f80_0000u32, 0xc020_0000])
+        );
+        assert_eq!(bits.reinterpret(CellType::Float32).unwrap(), buf);
+
+        let buf = CellBuffer::from_vec(vec![-1i8, 1]);
+        assert_eq!(
+            buf.reinterpret(CellType::UInt8).unwrap(),
+            CellBuffer::from_vec(vec![255u8, 1])
+        );
+        assert!(buf.reinterpret(CellType::Int16).is_err());
+    }
+
+    #[test]
+    fn as_slice() {
+        let mut buf = CellBuffer::fill_via(4, |i| i as f32);
+        assert_eq!(buf.as_slice::<f32>().unwrap(), &[0.0, 1.0, 2.0, 3.0]);
+        assert!(buf.as_slice::<f64>().is_err());
+        assert!(buf.as_slice::<u32>().is_err());
+
+        buf.as_mut_slice::<f32>().unwrap()[1] = 5.0;
+        assert_eq!(buf.get(1), 5f32.into());
+        assert!(buf.as_mut_slice::<i32>().is_err());
+    }
+
+    #[test]
+    fn concat() {
+        let a = CellBuffer::from_vec(vec![1u8, 2]);
+        let b = CellBuffer::from_vec(vec![-3i16]);
+        let r = CellBuffer::concat(&[a.clone(), b.clone(), a.clone()]).unwrap();
+        assert_eq!(r, CellBuffer::from_vec(vec![1i16, 2, -3, 1, 2]));
+
+        let r = CellBuffer::concat(&[]).unwrap();
+        assert_eq!(r, CellBuffer::with_defaults(0, CellType::UInt8));
+
+        let mut r = a.clone();
+        r.append(&a).unwrap();
+        assert_eq!(r, CellBuffer::from_vec(vec![1u8, 2, 1, 2]));
+        r.append(&b).unwrap();
+        assert_eq!(r.cell_type(), CellType::Int16);
+        assert_eq!(r, CellBuffer::from_vec(vec![1i16, 2, 1, 2, -3]));
+    }
+
+    #[test]
+    fn slice() {
+        let buf = CellBuffer::fill_via(5, |i| i as u16);
+        assert_eq!(buf.slice(..), buf);
+        assert_eq!(buf.slice(2..), CellBuffer::from_vec(vec![2u16, 3, 4]));
+        assert_eq!(buf.slice(1..3), CellBuffer::from_vec(vec![1u16, 2]));
+        assert_eq!(buf.slice(..=1), CellBuffer::from_vec(vec![0u16, 1]));
+        assert!(buf.slice(5..).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn slice_out_of_range() {
+        CellBuffer::fill_via(5, |i| i as u16).slice(2..6);
+    }
+
+    #[test]
+    fn count_nonfinite() {
+        let buf = CellBuffer::from_vec(vec![
+            1.0,
+            f64::NAN,
+            f64::INFINITY,
+            -f64::NAN,
+            f64::NEG_INFINITY,
+        ]);
+        assert_eq!(buf.count_nans(), 2);
+        assert_eq!(buf.count_nonfinite(), 4);
+        let buf = CellBuffer::from_vec(vec![f32::NAN, 0.0]);
+        assert_eq!((buf.count_nans(), buf.count_nonfinite()), (1, 1));
+        let buf = CellBuffer::fill_via(5, |i| i as i32);
+        assert_eq!((buf.count_nans(), buf.count_nonfinite()), (0, 0));
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = CellBuffer::fill_via(10, |i| i as f64 / 3.0);
+        let b = CellBuffer::fill_via(10, |i| (i as f64 / 3.0) as f32);
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-6));
+        assert!(!a.approx_eq(&b, 1e-12));
+        assert!(!a.approx_eq(&a.slice(1..), 1e-6));
+        let c = &a + &CellBuffer::fill(10, 0.1.into());
+        assert!(!a.approx_eq(&c, 1e-6));
+
+        let n = CellBuffer::from_vec(vec![f64::NAN, f64::INFINITY]);
+        assert!(n.approx_eq(&n.clone(), 0.0));
+
+        let i = CellBuffer::fill_via(10, |i| i as u16);
+        assert!(i.approx_eq(&i.convert(CellType::Int32).unwrap(), 0.0));
+        assert!(!i.approx_eq(&CellBuffer::fill_via(10, |i| i as u16 + 1), 2.0));
+    }
+
+    #[test]
+    fn lerp() {
+        let a = CellBuffer::from_vec(vec![0u8, 10, 20]);
+        let b = CellBuffer::from_vec(vec![10i16, 10, -20]);
+        let r = a.lerp(&b, 0.25).unwrap();
+        assert_eq!(r, vec![2.5, 10.0, 10.0]);
+        assert_eq!(
+            a.lerp(&a.slice(..0), 0.5).unwrap_err().to_string(),
+            "Length mismatch between 3 and 0"
+        );
+        let empty = CellBuffer::default();
+        assert_eq!(
+            empty.lerp(&empty, 0.5).unwrap().cell_type(),
+            CellType::Float64
+        );
+    }
+
+    #[test]
+    fn fold_reduce() {
+        let buf = CellBuffer::from_vec(vec![0b0011u8, 0b0101, 0b1000]);
+        let xor = buf.fold(0u8, |acc, v| acc ^ v.get::<u8>().unwrap());
+        assert_eq!(xor, 0b1110);
+        let any_odd = buf.fold(false, |acc, v| acc || v.get::<u8>().unwrap() % 2 == 1);
+        assert!(any_odd);
+        assert_eq!(buf.reduce(|a, b| a * b), Some(CellValue::Float64(120.0)));
+        assert_eq!(CellBuffer::default().reduce(|a, b| a + b), None);
+    }
+
+    #[test]
+    fn to_vec_lossy() {
+        let buf = CellBuffer::from_vec(vec![-3.5, 0.4, 127.5, 300.0, f64::NAN, f64::INFINITY]);
+        assert_eq!(
+            buf.clone().to_vec_lossy::<u8>(),
+            vec![0, 0, 128, 255, 0, 255]
+        );
+        assert_eq!(buf.to_vec_lossy::<i8>(), vec![-4, 0, 127, 127, 0, 127]);
+        let buf = CellBuffer::fill_via(3, |i| i as u8);
+        assert_eq!(
+            buf.clone().to_vec_lossy::<f32>(),
+            buf.to_vec::<f32>().unwrap()
+        );
+    }
+
+    #[test]
+    fn scale_offset() {
+        let buf = CellBuffer::from_vec(vec![0u16, 100, 65535]);
+        let r = buf.scale_offset(0.5, -10.0);
+        assert_eq!(r, CellBuffer::from_vec(vec![-10.0, 40.0, 32757.5]));
+        let buf = CellBuffer::from_vec(vec![i64::MIN, 0]);
+        assert_eq!(buf.scale_offset(1.0, 0.0).cell_type(), CellType::Float64);
+        assert!(CellBuffer::default().scale_offset(2.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn map_into() {
+        let buf = CellBuffer::from_vec(vec![-3.5, 0.4, 127.6, 300.0]);
+        let r: Vec<u8> = buf.map_into(|v| v.get::<f64>().unwrap().round().clamp(0.0, 255.0) as u8);
+        assert_eq!(r, vec![0, 0, 128, 255]);
+    }
+
+    #[test]
+    fn filter() {
+        let buf = CellBuffer::fill_via(6, |i| i as u8);
+        let even = buf.filter(|v| v.get::<u8>().unwrap() % 2 == 0);
+        assert_eq!(even, CellBuffer::from_vec(vec![0u8, 2, 4]));
+        let none = buf.filter(|_| false);
+        assert!(none.is_empty());
+        assert_eq!(none.cell_type(), CellType::UInt8);
+    }
+
+    #[test]
+    fn split_at() {
+        let buf = CellBuffer::fill_via(5, |i| i as i32);
+        for mid in 0..=5 {
+            let (l, r) = buf.split_at(mid);
+            assert_eq!(l.len(), mid);
+            assert_eq!(r.cell_type(), CellType::Int32);
+            assert_eq!(CellBuffer::concat(&[l, r]).unwrap(), buf);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "mid > len")]
+    fn split_at_out_of_range() {
+        CellBuffer::fill_via(5, |i| i as u16).split_at(6);
+    }
+
+    #[test]
+    fn chunks() {
+        let buf = CellBuffer::fill_via(6, |i| i as f32);
+        let chunks: Vec<_> = buf.chunks(3).collect();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1], CellBuffer::from_vec(vec![3f32, 4., 5.]));
+
+        let chunks: Vec<_> = buf.chunks(4).collect();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), 4);
+        assert_eq!(chunks[1], CellBuffer::from_vec(vec![4f32, 5.]));
+        assert!(chunks.iter().all(|c| c.cell_type() == CellType::Float32));
+
+        assert_eq!(buf.chunks(10).count(), 1);
+        assert_eq!(
+            CellBuffer::with_defaults(0, CellType::UInt8)
+                .chunks(2)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn chunks_zero() {
+        let _ = CellBuffer::fill_via(6, |i| i as f32).chunks(0);
+    }
+
+    #[test]
+    fn entropy() {
+        let buf = CellBuffer::fill_via(8, |i| (i % 2) as u8);
+        assert_eq!(buf.value_counts().get(&0u8.into()), Some(&4));
+        assert_eq!(buf.shannon_entropy(), 1.0);
+        assert_eq!(buf.entropy_per_class().get(&1u8.into()), Some(&0.5));
+
+        let buf = CellBuffer::fill(8, 3.5.into());
+        assert_eq!(buf.shannon_entropy(), 0.0);
+
+        let buf = CellBuffer::fill_via(4, |i| i as i32);
+        assert_eq!(buf.shannon_entropy(), 2.0);
+
+        assert_eq!(
+            CellBuffer::with_defaults(0, CellType::UInt8).shannon_entropy(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn reclassify() {
+        use CellValue::*;
+        let labels = CellBuffer::from_vec(vec![1u8, 2, 3, 4, 2, 9]);
+        let table = [
+            (UInt8(1), UInt8(10)),
+            (UInt8(2), UInt8(20)),
+            (Int32(3), UInt8(30)),
+            (UInt8(2), UInt8(99)),
+        ];
+
+        let r = labels.reclassify(&table, None).unwrap();
+        assert_eq!(r, CellBuffer::from_vec(vec![10u8, 20, 30, 4, 20, 9]));
+
+        let r = labels.reclassify(&table, Some(Int16(-1))).unwrap();
+        assert_eq!(r, CellBuffer::from_vec(vec![10i16, 20, 30, -1, 20, -1]));
+
+        let r = labels
+            .reclassify(&[(UInt8(4), Float32(0.5))], None)
+            .unwrap();
+        assert_eq!(
+            r,
+            CellBuffer::from_vec(vec![1.0f32, 2.0, 3.0, 0.5, 2.0, 9.0])
+        );
+
+        assert_eq!(labels.reclassify(&[], None).unwrap(), labels);
+
+        let r = labels.reclassify(&[(UInt8(1), Int8(-1))], None);
+        assert!(r.is_ok());
+        let big = CellBuffer::from_vec(vec![1u8, 200]);
+        let r = big.reclassify(&[(UInt8(1), Int8(-1))], None);
+        assert!(matches!(r, Err(Error::NarrowingError { .. })));
+    }
+
+    #[test]
+    fn clamp_to() {
+        let mut buf = CellBuffer::from_vec(vec![-50i16, 0, 42, 100, 250]);
+        buf.clamp_to(0u8.into(), 100.into()).unwrap();
+        assert_eq!(buf, CellBuffer::from_vec(vec![0i16, 0, 42, 100, 100]));
+        assert_eq!(buf.cell_type(), CellType::Int16);
+
+        let buf = CellBuffer::from_vec(vec![-1.5f32, f32::NAN, 3.0]);
+        let r = buf.clamped((-1).into(), 1.into()).unwrap();
+        assert_eq!(r.get(0), CellValue::Float32(-1.0));
+        assert!(r.get(1).get::<f32>().unwrap().is_nan());
+        assert_eq!(r.get(2), CellValue::Float32(1.0));
+
+        let mut buf = CellBuffer::from_vec(vec![1u8, 2]);
+        assert!(matches!(
+            buf.clamp_to((-1).into(), 1.into()),
+            Err(Error::NarrowingError { .. })
+        ));
+        assert!(matches!(
+            buf.clamp_to(0.5.into(), 1.into()),
+            Err(Error::NarrowingError { .. })
+        ));
+        assert!(matches!(
+            buf.clamp_to(2.into(), 1.into()),
+            Err(Error::ValidationError(_))
+        ));
+        assert_eq!(buf, CellBuffer::from_vec(vec![1u8, 2]));
+    }
+
+    #[test]
+    fn count_in_range() {
+        let buf = CellBuffer::from_vec(vec![0u8, 5, 10, 15]);
+        assert_eq!(buf.count_in_range(4.into(), 11.into()), 2);
+        assert_eq!(buf.count_out_of_range(4.into(), 11.into()), 2);
+        assert_eq!(buf.count_in_range(5.0.into(), 10i64.into()), 2);
+        assert_eq!(buf.count_in_range((-1i8).into(), 15.into()), 4);
+        assert_eq!(buf.count_in_range(11.into(), 4.into()), 0);
+    }
+
+    #[test]
+    fn dot() {
+        let a = CellBuffer::from_vec(vec![1i16, -2, 3, 300]);
+        let b = CellBuffer::from_vec(vec![4i16, 5, -6, 200]);
+        // 4 - 10 - 18
+        assert!(matches!(
+            a.slice(..3).dot(&b.slice(..3)).unwrap(),
+            CellValue::Int16(-24)
+        ));
+        // 4 - 10 - 18 + 60000 overflows `i16`.
+        assert!(matches!(a.dot(&b).unwrap(), CellValue::Float64(v) if v == 59976.0));
+        let c = CellBuffer::from_vec(vec![0.5f32, 1.0, 0.0, 0.0]);
+        assert!(matches!(a.dot(&c).unwrap(), CellValue::Float32(v) if v == -1.5));
+
+        // Exact beyond the 53-bit precision of `f64`.
+        let big = CellBuffer::from_vec(vec![3_000_000_000i64, 1]);
+        let r = big
+            .dot(&CellBuffer::from_vec(vec![3_000_000_001i64, 1]))
+            .unwrap();
+        assert!(matches!(r, CellValue::Int64(9_000_000_003_000_000_001)));
+
+        assert!(matches!(
+            CellBuffer::default().dot(&CellBuffer::default()).unwrap(),
+            CellValue::UInt8(0)
+        ));
+        assert!(matches!(
+            a.dot(&b.slice(..2)),
+            Err(Error::LengthMismatch { lhs: 4, rhs: 2 })
+        ));
+    }
+
+    #[test]
+    fn pearson() {
+        let x = CellBuffer::fill_via(10, |i| i as u8);
+        let y = CellBuffer::fill_via(10, |i| i as f32 * 2.5 + 3.0);
+        assert!((x.pearson(&y).unwrap() - 1.0).abs() < 1e-12);
+        let y = CellBuffer::fill_via(10, |i| -(i as i32) * 3);
+        assert!((x.pearson(&y).unwrap() + 1.0).abs() < 1e-12);
+        assert!(x.pearson(&CellBuffer::fill(10, 1.into())).unwrap().is_nan());
+        assert!(x.pearson(&x.slice(1..)).is_err());
+    }
+
+    #[test]
+    fn to_string_vec() {
+        let buf = CellBuffer::from_vec(vec![1.0, -2.5, 1e-3]);
+        assert_eq!(buf.to_string_vec(), ["1", "-2.5", "0.001"]);
+    }
+
     #[test]
     fn debug() {
         let b = CellBuffer::fill(5, 37.into());
@@ -581,7 +2581,7 @@ mod tests {
     fn unary() {
         use num_traits::One;
         macro_rules! test {
-            ($( ($id:ident, $p:ident) ),*) => {$({
+            ($( ($id:ident, $p:ty) ),*) => {$({
                 let one: CellValue = <$p>::one().into();
                 let buf = -CellBuffer::fill(3, one);
                 assert_eq!(buf.get(0), -one);
@@ -613,11 +2613,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn same_type_fast_path() {
+        // Compare against the per-`CellValue` computation the slow path performs.
+        fn slow(
+            l: &CellBuffer,
+            r: &CellBuffer,
+            f: fn(CellValue, CellValue) -> CellValue,
+        ) -> CellBuffer {
+            l.into_iter().zip(r).map(|(a, b)| f(a, b)).collect()
+        }
+        for ct in CellType::iter() {
+            let (lhs, rhs) = if ct.is_signed() {
+                let lhs = CellBuffer::fill_via(17, |i| i as i8 * 7 - 60);
+                (lhs, CellBuffer::fill_via(17, |i| (i % 5) as i8 - 2))
+            } else {
+                let lhs = CellBuffer::fill_via(17, |i| i as u8 * 7);
+                (lhs, CellBuffer::fill_via(17, |i| (i % 5) as u8))
+            };
+            let (lhs, rhs) = (lhs.convert(ct).unwrap(), rhs.convert(ct).unwrap());
+            assert_eq!(&lhs + &rhs, slow(&lhs, &rhs, |a, b| a + b), "{ct}");
+            assert_eq!(&lhs - &rhs, slow(&lhs, &rhs, |a, b| a - b), "{ct}");
+            assert_eq!(&lhs * &rhs, slow(&lhs, &rhs, |a, b| a * b), "{ct}");
+            assert_eq!(&lhs / &rhs, slow(&lhs, &rhs, |a, b| a / b), "{ct}");
+        }
+
+        let lhs = CellBuffer::from_vec(vec![f32::NAN, f32::INFINITY, -0.0, 1.5]);
+        let rhs = CellBuffer::from_vec(vec![1.0f32, f32::INFINITY, 0.0, 0.1]);
+        assert_eq!(&lhs - &rhs, slow(&lhs, &rhs, |a, b| a - b));
+        assert_eq!(&lhs / &rhs, slow(&lhs, &rhs, |a, b| a / b));
+
+        // Empty results remain `UInt8`.
+        let empty = CellBuffer::with_defaults(0, CellType::Float32);
+        assert_eq!((&empty + &empty).cell_type(), CellType::UInt8);
+    }
+
+    #[test]
+    fn try_binary() {
+        let lhs = CellBuffer::fill_via(3, |i| i as u8);
+        let rhs = CellBuffer::fill_via(3, |i| i as i16 + 1);
+        assert_eq!(lhs.try_add(&rhs).unwrap(), &lhs + &rhs);
+        assert_eq!(lhs.try_div(&rhs).unwrap(), &lhs / &rhs);
+        let err = lhs.try_sub(&rhs.slice(..2)).unwrap_err();
+        assert!(matches!(err, Error::LengthMismatch { lhs: 3, rhs: 2 }));
+        assert_eq!(err.to_string(), "Length mismatch between 3 and 2");
+        assert!(lhs.try_mul(&CellBuffer::default()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch between 3 and 2")]
+    fn binary_length_mismatch() {
+        let lhs = CellBuffer::fill_via(3, |i| i as u8);
+        let _ = &lhs * &lhs.slice(..2);
+    }
+
     #[test]
     fn scalar() {
         let buf = CellBuffer::fill_via(9, |i| i as u8 + 1);
-        let r = buf * 2.0;
+        let r = buf.clone() * 2.0;
         assert_eq!(r, CellBuffer::fill_via(9, |i| (i as f64 + 1.0) * 2.0));
+        assert_eq!(2.0 * &buf, r);
+        assert_eq!(2.0 * buf.clone(), buf.clone() * 2.0);
+        assert_eq!(1u8 + &buf, buf.clone() + 1u8);
+
+        let r = 10 - &buf;
+        assert_eq!(r, CellBuffer::fill_via(9, |i| 10.0 - (i as f64 + 1.0)));
+        let r = 1.0f32 / buf;
+        assert_eq!(r.get(1), CellValue::Float64(0.5));
+    }
+
+    #[test]
+    fn equal_vec() {
+        let buf = CellBuffer::from_vec(vec![1u8, 2, 3]);
+        assert_eq!(buf, vec![1u8, 2, 3]);
+        assert_eq!(buf, &[1u8, 2, 3][..]);
+        assert_ne!(buf, vec![1u8, 2]);
+        assert_ne!(buf, vec![1u8, 2, 4]);
+        // Cell-types must match.
+        assert_ne!(buf, vec![1u16, 2, 3]);
+        assert_ne!(buf, vec![1.0f64, 2.0, 3.0]);
+
+        let buf = CellBuffer::from_vec(vec![f32::NAN, 1.0]);
+        assert_eq!(buf, vec![f32::NAN, 1.0]);
     }
 
     #[test]
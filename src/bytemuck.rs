@@ -0,0 +1,73 @@
+//! `erased-cells` connector to the `bytemuck` crate.
+
+use bytemuck::{cast_slice, pod_read_unaligned};
+
+use crate::error::{Error, Result};
+use crate::{with_ct, CellBuffer, CellType};
+
+impl CellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    /// View the buffer's cells as bytes, in native-endian order, without copying.
+    pub fn as_bytes(&self) -> &[u8] {
+        macro_rules! as_bytes {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => cast_slice(v),)*
+                }
+            };
+        }
+        with_ct!(as_bytes)
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    /// Construct a buffer of cell-type `ct` by reinterpreting native-endian `bytes`.
+    ///
+    /// `bytes` need not be aligned for `ct`.
+    ///
+    /// Returns `Err(ByteLengthError)` if `bytes.len()` is not a multiple of `ct.size_of()`.
+    pub fn from_pod_bytes(ct: CellType, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() % ct.size_of() != 0 {
+            return Err(Error::ByteLengthError { ct, len: bytes.len() });
+        }
+        macro_rules! from_pod_bytes {
+            ( $(($id:ident, $p:ty)),*) => {
+                match ct {
+                    $(CellType::$id => CellBuffer::$id(
+                        bytes
+                            .chunks_exact(ct.size_of())
+                            .map(pod_read_unaligned::<$p>)
+                            .collect(),
+                    ),)*
+                }
+            };
+        }
+        Ok(with_ct!(from_pod_bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufferOps, CellBuffer, CellType};
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn matches_le_bytes() {
+        for ct in CellType::iter() {
+            let mut buf = CellBuffer::with_defaults(4, ct);
+            buf.put(1, ct.max_value()).unwrap();
+            buf.put(2, ct.min_value()).unwrap();
+            assert_eq!(buf.as_bytes(), buf.to_le_bytes(), "{ct}");
+            assert_eq!(CellBuffer::from_pod_bytes(ct, buf.as_bytes()).unwrap(), buf);
+        }
+    }
+
+    #[test]
+    fn unaligned() {
+        let buf = CellBuffer::fill_via(4, |i| i as f64);
+        let mut bytes = vec![0u8];
+        bytes.extend_from_slice(buf.as_bytes());
+        let r = CellBuffer::from_pod_bytes(CellType::Float64, &bytes[1..]).unwrap();
+        assert_eq!(r, buf);
+        assert!(CellBuffer::from_pod_bytes(CellType::Float64, &bytes).is_err());
+    }
+}
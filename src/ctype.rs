@@ -1,5 +1,7 @@
 use crate::error::Error;
 use crate::{with_ct, CellValue};
+#[cfg(feature = "f16")]
+use half::f16;
 use num_traits::{One, Zero};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -9,9 +11,12 @@ use std::str::FromStr;
 
 /// CellType enum constructor.
 macro_rules! cv_enum {
-    ( $(($id:ident, $_p:ident)),*) => {
+    ( $(($id:ident, $_p:ty)),*) => {
         /// Cell-type variants
-        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        ///
+        /// Non-exhaustive, as the set of variants depends on enabled features (e.g. `f16`).
+        #[non_exhaustive]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         #[repr(u8)]
         pub enum CellType { $($id),* }
@@ -26,29 +31,73 @@ impl Display for CellType {
     }
 }
 
+/// Get the unqualified name of primitive `T` (e.g. `f16` rather than `half::binary16::f16`).
+fn primitive_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Parses a `CellType` from its variant name (e.g. `UInt8`) or its primitive
+/// name (e.g. `u8`), ignoring case. `byte` is also accepted as an alias for `UInt8`.
 impl FromStr for CellType {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         macro_rules! str_match {
-            ( $( ($ct:ident, $_p:ident) ),* ) => {
-                match s {
-                    $( stringify!($ct) => Ok(CellType::$ct), )*
-                    o => Err(Error::ParseError(o.into(), "CellType")),
+            ( $( ($ct:ident, $p:ty) ),* ) => {
+                $(
+                if s.eq_ignore_ascii_case(stringify!($ct)) || s.eq_ignore_ascii_case(primitive_name::<$p>()) {
+                    return Ok(CellType::$ct);
                 }
+                )*
             };
         }
-        with_ct!(str_match)
+        with_ct!(str_match);
+
+        if s.eq_ignore_ascii_case("byte") {
+            Ok(CellType::UInt8)
+        } else {
+            Err(Error::ParseError(s.into(), "CellType"))
+        }
+    }
+}
+
+/// Converts a `repr(u8)` discriminant (i.e. `ct as u8`) back into a `CellType`.
+///
+/// Returns `Err(UnsupportedCellTypeError)` if `value` is not a valid discriminant.
+impl TryFrom<u8> for CellType {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        CellType::from_u8(value)
+            .ok_or_else(|| Error::UnsupportedCellTypeError(format!("discriminant {value}")))
     }
 }
 
 impl CellType {
-    /// Get an iterator over all the valid enumeration values.
-    pub fn iter() -> impl Iterator<Item = CellType> {
+    /// All the valid enumeration values, in declaration order.
+    pub const ALL: &'static [CellType] = {
         macro_rules! array {
-           ( $( ($id:ident, $_p:ident) ),+) => { [ $( CellType::$id, )+ ] };
+           ( $( ($id:ident, $_p:ty) ),+) => { &[ $( CellType::$id, )+ ] };
         }
-        with_ct!(array).into_iter()
+        with_ct!(array)
+    };
+
+    /// The number of valid enumeration values.
+    pub const COUNT: usize = Self::ALL.len();
+
+    /// Get the `CellType` with `repr(u8)` discriminant `value` (i.e. `ct as u8`), if any.
+    ///
+    /// **Note**: Discriminants are assigned in declaration order, so they depend on the
+    /// enabled features (e.g. `f16` inserts `Float16` before `Float32`). Bytes written
+    /// by a build with a different feature set may decode to a different `CellType`.
+    pub fn from_u8(value: u8) -> Option<CellType> {
+        Self::ALL.iter().copied().find(|ct| *ct as u8 == value)
+    }
+
+    /// Get an iterator over all the valid enumeration values.
+    pub fn iter() -> impl DoubleEndedIterator<Item = CellType> + ExactSizeIterator {
+        Self::ALL.iter().copied()
     }
 
     /// Determine if `self` is integral or floating-point.
@@ -62,6 +111,8 @@ impl CellType {
             CellType::Int16 => true,
             CellType::Int32 => true,
             CellType::Int64 => true,
+            #[cfg(feature = "f16")]
+            CellType::Float16 => false,
             CellType::Float32 => false,
             CellType::Float64 => false,
         }
@@ -78,6 +129,8 @@ impl CellType {
             CellType::Int16 => true,
             CellType::Int32 => true,
             CellType::Int64 => true,
+            #[cfg(feature = "f16")]
+            CellType::Float16 => true,
             CellType::Float32 => true,
             CellType::Float64 => true,
         }
@@ -86,7 +139,7 @@ impl CellType {
     /// Number of bytes needed to encode `self`.
     pub fn size_of(&self) -> usize {
         macro_rules! size_of {
-            ($( ($id:ident, $p:ident) ),* ) => {
+            ($( ($id:ident, $p:ty) ),* ) => {
                 match self {
                     $(CellType::$id => mem::size_of::<$p>()),*
                 }
@@ -95,6 +148,40 @@ impl CellType {
         with_ct!(size_of)
     }
 
+    /// Human-friendly description of `self` for display purposes, e.g. `"8-bit unsigned integer"`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            CellType::UInt8 => "8-bit unsigned integer",
+            CellType::UInt16 => "16-bit unsigned integer",
+            CellType::UInt32 => "32-bit unsigned integer",
+            CellType::UInt64 => "64-bit unsigned integer",
+            CellType::Int8 => "8-bit signed integer",
+            CellType::Int16 => "16-bit signed integer",
+            CellType::Int32 => "32-bit signed integer",
+            CellType::Int64 => "64-bit signed integer",
+            #[cfg(feature = "f16")]
+            CellType::Float16 => "16-bit float",
+            CellType::Float32 => "32-bit float",
+            CellType::Float64 => "64-bit float",
+        }
+    }
+
+    /// Number of bits needed to encode `self`.
+    pub fn bits(&self) -> u32 {
+        self.size_of() as u32 * 8
+    }
+
+    /// Machine epsilon for floating-point cell-types, or `None` for integral ones.
+    pub fn epsilon(&self) -> Option<CellValue> {
+        match self {
+            #[cfg(feature = "f16")]
+            CellType::Float16 => Some(f16::EPSILON.into()),
+            CellType::Float32 => Some(f32::EPSILON.into()),
+            CellType::Float64 => Some(f64::EPSILON.into()),
+            _ => None,
+        }
+    }
+
     /// Select the `CellType` that can numerically contain both `self` and `other`.
     pub fn union(self, other: Self) -> Self {
         let min_bytes = {
@@ -116,6 +203,8 @@ impl CellType {
             (1, true, true) => Self::Int8,
             (2, false, true) => Self::UInt16,
             (2, true, true) => Self::Int16,
+            #[cfg(feature = "f16")]
+            (2, _, false) => Self::Float16,
             (4, false, true) => Self::UInt32,
             (4, true, true) => Self::Int32,
             (4, _, false) => Self::Float32,
@@ -125,6 +214,54 @@ impl CellType {
         }
     }
 
+    /// Select the `CellType` that can numerically contain all of `types`, by folding
+    /// [`union`][Self::union] across them.
+    ///
+    /// Returns `None` if `types` is empty.
+    pub fn common(types: impl IntoIterator<Item = CellType>) -> Option<CellType> {
+        types.into_iter().reduce(CellType::union)
+    }
+
+    /// Select the smallest signed `CellType` that can contain all values of `self`.
+    ///
+    /// Signed and floating-point types are returned unchanged. `UInt64` saturates to
+    /// `Int64`, as there is no wider signed integral type; values above `i64::MAX`
+    /// will not fit.
+    pub fn to_signed(self) -> Self {
+        match self {
+            CellType::UInt8 => CellType::Int16,
+            CellType::UInt16 => CellType::Int32,
+            CellType::UInt32 | CellType::UInt64 => CellType::Int64,
+            o => o,
+        }
+    }
+
+    /// Select the unsigned `CellType` of the same width as `self`.
+    ///
+    /// Unsigned and floating-point types are returned unchanged. Negative values
+    /// will not fit in the result.
+    pub fn to_unsigned(self) -> Self {
+        match self {
+            CellType::Int8 => CellType::UInt8,
+            CellType::Int16 => CellType::UInt16,
+            CellType::Int32 => CellType::UInt32,
+            CellType::Int64 => CellType::UInt64,
+            o => o,
+        }
+    }
+
+    /// Select the smallest floating-point `CellType` that can contain all values of `self`.
+    ///
+    /// Floating-point types are returned unchanged. `UInt64` and `Int64` map to `Float64`,
+    /// which cannot represent all their values exactly.
+    pub fn to_float(self) -> Self {
+        if self.is_integral() {
+            self.union(CellType::Float32)
+        } else {
+            self
+        }
+    }
+
     /// Determine of `self` can fit within `other`.
     pub fn can_fit_into(self, other: Self) -> bool {
         self.union(other) == other
@@ -133,7 +270,7 @@ impl CellType {
     /// Construct the zero value for a variant.
     pub fn zero(&self) -> CellValue {
         macro_rules! zero {
-           ( $( ($id:ident, $p:ident) ),+) => {
+           ( $( ($id:ident, $p:ty) ),+) => {
                match self {
                    $(Self::$id => <$p>::zero().into(),)*
                }
@@ -145,7 +282,7 @@ impl CellType {
     /// Construct the one value for a variant.
     pub fn one(&self) -> CellValue {
         macro_rules! one {
-           ( $( ($id:ident, $p:ident) ),+) => {
+           ( $( ($id:ident, $p:ty) ),+) => {
                match self {
                    $(Self::$id => <$p>::one().into(),)*
                }
@@ -157,9 +294,9 @@ impl CellType {
     /// Determine the minimum value that can be represented by `self`.
     pub fn min_value(&self) -> CellValue {
         macro_rules! mins {
-            ( $( ($id:ident, $p:ident) ),* ) => {
+            ( $( ($id:ident, $p:ty) ),* ) => {
                 match self {
-                    $( CellType::$id => $p::MIN.into(), )*
+                    $( CellType::$id => <$p>::MIN.into(), )*
                 }
             };
         }
@@ -169,9 +306,9 @@ impl CellType {
     /// Determine the maximum value that can be represented by `self`.
     pub fn max_value(&self) -> CellValue {
         macro_rules! maxs {
-            ( $( ($id:ident, $p:ident) ),* ) => {
+            ( $( ($id:ident, $p:ty) ),* ) => {
                 match self {
-                    $( CellType::$id => $p::MAX.into(), )*
+                    $( CellType::$id => <$p>::MAX.into(), )*
                 }
             };
         }
@@ -184,6 +321,46 @@ mod tests {
     use crate::{with_ct, CellType};
     use std::str::FromStr;
 
+    #[cfg(feature = "f16")]
+    #[test]
+    fn float16_union() {
+        assert_eq!(
+            CellType::Float16.union(CellType::Float16),
+            CellType::Float16
+        );
+        assert_eq!(
+            CellType::Float16.union(CellType::Float32),
+            CellType::Float32
+        );
+        assert_eq!(
+            CellType::Float64.union(CellType::Float16),
+            CellType::Float64
+        );
+        assert_eq!(CellType::UInt8.union(CellType::Float16), CellType::Float16);
+        assert_eq!(CellType::Int16.union(CellType::Float16), CellType::Float32);
+        assert!(!CellType::Float16.is_integral());
+        assert_eq!(CellType::Float16.size_of(), 2);
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(CellType::ALL.len(), CellType::iter().count());
+        assert_eq!(CellType::COUNT, CellType::iter().len());
+        assert_eq!(CellType::iter().next_back(), Some(CellType::Float64));
+        assert_eq!(CellType::iter().next(), Some(CellType::UInt8));
+        assert!(CellType::ALL.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn from_u8() {
+        for ct in CellType::iter() {
+            assert_eq!(CellType::from_u8(ct as u8), Some(ct));
+            assert_eq!(CellType::try_from(ct as u8).unwrap(), ct);
+        }
+        assert_eq!(CellType::from_u8(CellType::COUNT as u8), None);
+        assert!(CellType::try_from(u8::MAX).is_err());
+    }
+
     #[test]
     fn can_union() {
         // reflexivity
@@ -228,14 +405,86 @@ mod tests {
         assert_eq!(CellType::Float64.size_of(), 8);
     }
 
+    #[test]
+    fn bits() {
+        assert_eq!(CellType::UInt8.bits(), 8);
+        assert_eq!(CellType::Float32.bits(), 32);
+        assert_eq!(CellType::Int64.bits(), 64);
+    }
+
+    #[test]
+    fn common() {
+        use CellType::*;
+        assert_eq!(CellType::common([UInt8, Int16, Float32]), Some(Float32));
+        assert_eq!(CellType::common([UInt16]), Some(UInt16));
+        assert_eq!(CellType::common([UInt8, Int8]), Some(Int16));
+        assert_eq!(CellType::common([]), None);
+    }
+
+    #[test]
+    fn to_signed() {
+        assert_eq!(CellType::UInt8.to_signed(), CellType::Int16);
+        assert_eq!(CellType::UInt16.to_signed(), CellType::Int32);
+        assert_eq!(CellType::UInt32.to_signed(), CellType::Int64);
+        assert_eq!(CellType::UInt64.to_signed(), CellType::Int64);
+        assert_eq!(CellType::Int8.to_signed(), CellType::Int8);
+        assert_eq!(CellType::Float32.to_signed(), CellType::Float32);
+        for ct in CellType::iter() {
+            assert!(ct.to_signed().is_signed(), "{ct}");
+        }
+    }
+
+    #[test]
+    fn to_unsigned() {
+        assert_eq!(CellType::Int8.to_unsigned(), CellType::UInt8);
+        assert_eq!(CellType::Int16.to_unsigned(), CellType::UInt16);
+        assert_eq!(CellType::Int32.to_unsigned(), CellType::UInt32);
+        assert_eq!(CellType::Int64.to_unsigned(), CellType::UInt64);
+        assert_eq!(CellType::UInt16.to_unsigned(), CellType::UInt16);
+        assert_eq!(CellType::Float64.to_unsigned(), CellType::Float64);
+    }
+
+    #[test]
+    fn to_float() {
+        assert_eq!(CellType::UInt8.to_float(), CellType::Float32);
+        assert_eq!(CellType::Int16.to_float(), CellType::Float32);
+        assert_eq!(CellType::Int32.to_float(), CellType::Float64);
+        assert_eq!(CellType::UInt64.to_float(), CellType::Float64);
+        assert_eq!(CellType::Float32.to_float(), CellType::Float32);
+        for ct in CellType::iter() {
+            assert!(!ct.to_float().is_integral(), "{ct}");
+        }
+    }
+
+    #[test]
+    fn label() {
+        assert_eq!(CellType::UInt8.label(), "8-bit unsigned integer");
+        assert_eq!(CellType::Int32.label(), "32-bit signed integer");
+        assert_eq!(CellType::Float64.label(), "64-bit float");
+        for ct in CellType::iter() {
+            assert!(
+                ct.label().starts_with(&format!("{}-bit", ct.bits())),
+                "{ct}"
+            );
+        }
+    }
+
+    #[test]
+    fn epsilon() {
+        assert!(CellType::UInt8.epsilon().is_none());
+        assert!(CellType::Int64.epsilon().is_none());
+        assert_eq!(CellType::Float32.epsilon(), Some(f32::EPSILON.into()));
+        assert_eq!(CellType::Float64.epsilon(), Some(f64::EPSILON.into()));
+    }
+
     #[test]
     fn has_min_max() {
         // Confirm min/max returns correct values.
         macro_rules! test {
-            ( $( ($ct:ident, $p:ident) ),* ) => {
+            ( $( ($ct:ident, $p:ty) ),* ) => {
                 $(
-                    assert_eq!(CellType::$ct.min_value(), $p::MIN.into(), "min");
-                    assert_eq!(CellType::$ct.max_value(), $p::MAX.into(), "max");
+                    assert_eq!(CellType::$ct.min_value(), <$p>::MIN.into(), "min");
+                    assert_eq!(CellType::$ct.max_value(), <$p>::MAX.into(), "max");
                 )*
             };
         }
@@ -246,7 +495,7 @@ mod tests {
     fn can_string() {
         // Confirm simple serialization.
         macro_rules! test {
-            ( $( ($ct:ident, $p:ident) ),* ) => {
+            ( $( ($ct:ident, $p:ty) ),* ) => {
                 $( assert_eq!(CellType::$ct.to_string(), stringify!($ct)); )*
             };
         }
@@ -263,10 +512,22 @@ mod tests {
         assert!(CellType::from_str("UInt57").is_err());
     }
 
+    #[test]
+    fn can_parse_aliases() {
+        for s in ["u8", "UINT8", "uint8", "byte", "Byte"] {
+            assert_eq!(CellType::from_str(s).unwrap(), CellType::UInt8, "{s}");
+        }
+        assert_eq!(CellType::from_str("f64").unwrap(), CellType::Float64);
+        assert_eq!(CellType::from_str("float64").unwrap(), CellType::Float64);
+        assert_eq!(CellType::from_str("i16").unwrap(), CellType::Int16);
+        assert!(CellType::from_str("u57").is_err());
+        assert!(CellType::from_str("").is_err());
+    }
+
     #[test]
     fn zero_one() {
         macro_rules! test {
-            ( $( ($ct:ident, $p:ident) ),* ) => {
+            ( $( ($ct:ident, $p:ty) ),* ) => {
                 $({
                     let zero = CellType::$ct.zero();
                     let one = CellType::$ct.one();
@@ -2,37 +2,100 @@ use super::*;
 use num_traits::{One, Zero};
 use std::fmt::Debug;
 
+mod sealed {
+    pub trait Sealed {}
+}
+
 /// Trait for marking Rust primitives as having a corresponding [`CellType`].
 ///
 /// For example, [`f64`] is [`CellEncoding`] through [`CellType::Float64`],
 /// but [`isize`] is not `CellEncoding`.
-pub trait CellEncoding: Copy + Debug + Default + Zero + One + PartialEq {
+///
+/// This trait is sealed: the crate's internal casts rely on it being implemented only
+/// for the primitives enumerated by [`CellType`], so it cannot be implemented outside
+/// this crate. Every other bound is satisfied below, so the only error is the missing
+/// `Sealed` implementation (`E0277`):
+///
+/// ```compile_fail,E0277
+/// use erased_cells::{CellEncoding, CellType, CellValue};
+///
+/// #[derive(Debug, Default, Clone, Copy, PartialEq)]
+/// struct Bogus(u8);
+/// # impl std::ops::Add for Bogus { type Output = Self; fn add(self, r: Self) -> Self { Bogus(self.0 + r.0) } }
+/// # impl std::ops::Mul for Bogus { type Output = Self; fn mul(self, r: Self) -> Self { Bogus(self.0 * r.0) } }
+/// # impl num_traits::Zero for Bogus { fn zero() -> Self { Bogus(0) } fn is_zero(&self) -> bool { self.0 == 0 } }
+/// # impl num_traits::One for Bogus { fn one() -> Self { Bogus(1) } }
+///
+/// impl CellEncoding for Bogus {
+///     const MIN: Self = Bogus(0);
+///     const MAX: Self = Bogus(u8::MAX);
+///     fn nodata_default() -> Self { Bogus(0) }
+///     fn cell_type() -> CellType { CellType::UInt8 }
+///     fn into_cell_value(self) -> CellValue { CellValue::UInt8(self.0) }
+///     fn from_cell_value(_: CellValue) -> Option<Self> { None }
+/// }
+/// ```
+pub trait CellEncoding: sealed::Sealed + Copy + Debug + Default + Zero + One + PartialEq {
+    /// The smallest value representable by `Self`.
+    const MIN: Self;
+    /// The largest value representable by `Self`.
+    const MAX: Self;
+    /// The conventional no-data value for `Self`: `MIN` for integral types and `NaN` for
+    /// floating-point types, as used by `NoData::Default`.
+    fn nodata_default() -> Self;
     /// Returns the [`CellType`] covering `Self`.
     fn cell_type() -> CellType;
     /// Converts `self` into a [`CellValue`].
     fn into_cell_value(self) -> CellValue;
+    /// Extracts `Self` from a [`CellValue`] variant of the <u>exact</u> same type,
+    /// without conversion. Returns `None` otherwise.
+    fn from_cell_value(value: CellValue) -> Option<Self>;
     /// Convert dynamic type to static type when logically known.
     /// Returns `None` if given value isn't actually the <u>exact</u> same
     /// type as encoding.
     fn static_cast<T: CellEncoding + Sized>(value: T) -> Option<Self> {
-        if Self::cell_type() == T::cell_type() {
-            Some(unsafe { std::mem::transmute_copy::<T, Self>(&value) })
-        } else {
-            None
-        }
+        Self::from_cell_value(value.into_cell_value())
     }
 }
 
+/// Selects the conventional no-data value for a cell-type's primitive.
+macro_rules! nodata_default {
+    (Float16, $prim:ty) => {
+        <$prim>::NAN
+    };
+    (Float32, $prim:ty) => {
+        <$prim>::NAN
+    };
+    (Float64, $prim:ty) => {
+        <$prim>::NAN
+    };
+    ($ct:ident, $prim:ty) => {
+        <$prim>::MIN
+    };
+}
+
 /// Implements [`CellEncoding`] for each cell-type.
 macro_rules! encoding {
-    ( $( ($ct:ident, $prim:ident) ),* ) => { $(
+    ( $( ($ct:ident, $prim:ty) ),* ) => { $(
+        impl sealed::Sealed for $prim {}
         impl CellEncoding for $prim {
+            const MIN: Self = <$prim>::MIN;
+            const MAX: Self = <$prim>::MAX;
+            fn nodata_default() -> Self {
+                nodata_default!($ct, $prim)
+            }
             fn cell_type() -> CellType {
                 CellType::$ct
             }
             fn into_cell_value(self) -> CellValue {
                 CellValue::$ct(self)
             }
+            fn from_cell_value(value: CellValue) -> Option<Self> {
+                match value {
+                    CellValue::$ct(v) => Some(v),
+                    _ => None,
+                }
+            }
         } )*
     };
 }
@@ -42,9 +105,29 @@ with_ct!(encoding);
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn constants() {
+        assert_eq!(<u8 as CellEncoding>::MAX, 255);
+        assert_eq!(<i16 as CellEncoding>::MIN, i16::MIN);
+        assert_eq!(<i32 as CellEncoding>::nodata_default(), i32::MIN);
+        assert!(<f32 as CellEncoding>::nodata_default().is_nan());
+    }
+
     #[test]
     fn casting() {
         assert!(<f64>::static_cast(34f64).is_some());
         assert!(<f64>::static_cast(34f32).is_none());
+
+        macro_rules! test {
+            ($( ($id:ident, $p:ty) ),*) => {$(
+                let v = <$p>::one();
+                assert_eq!(<$p>::static_cast(v), Some(v));
+                assert_eq!(<$p>::from_cell_value(CellValue::$id(v)), Some(v));
+                if CellType::$id != CellType::UInt8 {
+                    assert_eq!(<$p>::static_cast(1u8), None);
+                }
+            )*};
+        }
+        with_ct!(test);
     }
 }
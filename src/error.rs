@@ -9,7 +9,11 @@ use gdal::errors::GdalError;
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Enumeration of error kinds
+///
+/// New variants may be added in minor releases, so `match` expressions over `Error`
+/// outside of this crate must include a wildcard (`_`) arm.
 #[derive(ThisError, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("Invalid narrowing from cell-type {src} to {dst}")]
     NarrowingError { src: CellType, dst: CellType },
@@ -21,6 +25,16 @@ pub enum Error {
     ParseError(String, &'static str),
     #[error("Unable to convert {0} into NoData<{1}>::Value")]
     NoDataConversionError(f64, &'static str),
+    #[error("Byte length {len} is incompatible with cell-type {ct} of size {}", ct.size_of())]
+    ByteLengthError { ct: CellType, len: usize },
+    #[error("Length mismatch between {lhs} and {rhs}")]
+    LengthMismatch { lhs: usize, rhs: usize },
+    #[error("Unable to reinterpret cell-type {src} as {dst} of a different size")]
+    ReinterpretError { src: CellType, dst: CellType },
+    #[error("Invalid buffer state: {0}")]
+    ValidationError(String),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
     #[cfg(feature = "gdal")]
     #[error(transparent)]
     GdalError(#[from] GdalError),
@@ -0,0 +1,240 @@
+use super::{with_gdal_ct, RasterBandEx};
+use crate::error::*;
+use crate::{BufferOps, CellBuffer, CellType, CellValue, MaskedCellBuffer, NoData};
+use gdal::raster::{Buffer, GdalType, ResampleAlg};
+use gdal::{Dataset, DriverManager};
+use num_traits::{NumCast, ToPrimitive};
+use std::path::Path;
+
+/// Extension methods on [`Dataset`] to read all raster bands as [`CellBuffer`]s.
+pub trait DatasetEx {
+    #[cfg_attr(docsrs, doc(cfg(feature = "gdal")))]
+    /// Read a [`CellBuffer`] from each raster band of a GDAL [`Dataset`], in band order.
+    ///
+    /// Bands with differing cell-types are returned as-is, without unifying cell-types.
+    /// See [`RasterBandEx::read_cells`] for a description of the arguments.
+    ///
+    /// # Example
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use erased_cells::*;
+    /// use gdal::raster::Buffer;
+    /// use gdal::{Dataset, DriverManager};
+    ///
+    /// // Write a 3-band GeoTIFF to GDAL's in-memory filesystem.
+    /// let path = "/vsimem/read_all_cells.tiff";
+    /// let driver = DriverManager::get_driver_by_name("GTiff")?;
+    /// let ds = driver.create_with_band_type::<u8, _>(path, 4, 3, 3)?;
+    /// for b in 1..=3 {
+    ///     let data = vec![b as u8; 12];
+    ///     ds.rasterband(b)?.write((0, 0), (4, 3), &Buffer::new((4, 3), data))?;
+    /// }
+    /// drop(ds);
+    ///
+    /// let ds = Dataset::open(path)?;
+    /// let size = ds.raster_size();
+    /// let buffers = ds.read_all_cells((0, 0), size, size, None)?;
+    /// assert_eq!(buffers.len(), 3);
+    /// assert_eq!(buffers[2], CellBuffer::fill(12, 3u8.into()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn read_all_cells(
+        &self,
+        window: (isize, isize),
+        window_size: (usize, usize),
+        size: (usize, usize),
+        e_resample_alg: Option<ResampleAlg>,
+    ) -> Result<Vec<CellBuffer>>;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "gdal")))]
+    /// Read a [`MaskedCellBuffer`] from each raster band of a GDAL [`Dataset`], in band order.
+    ///
+    /// Each band's no-data value setting is used to construct its buffer's [`Mask`][crate::Mask].
+    /// See [`RasterBandEx::read_cells_masked`] for a description of the arguments.
+    fn read_all_cells_masked(
+        &self,
+        window: (isize, isize),
+        window_size: (usize, usize),
+        size: (usize, usize),
+        e_resample_alg: Option<ResampleAlg>,
+    ) -> Result<Vec<MaskedCellBuffer>>;
+}
+
+impl DatasetEx for Dataset {
+    fn read_all_cells(
+        &self,
+        window: (isize, isize),
+        window_size: (usize, usize),
+        size: (usize, usize),
+        e_resample_alg: Option<ResampleAlg>,
+    ) -> Result<Vec<CellBuffer>> {
+        (1..=self.raster_count())
+            .map(|i| {
+                self.rasterband(i)?
+                    .read_cells(window, window_size, size, e_resample_alg)
+            })
+            .collect()
+    }
+
+    fn read_all_cells_masked(
+        &self,
+        window: (isize, isize),
+        window_size: (usize, usize),
+        size: (usize, usize),
+        e_resample_alg: Option<ResampleAlg>,
+    ) -> Result<Vec<MaskedCellBuffer>> {
+        (1..=self.raster_count())
+            .map(|i| {
+                self.rasterband(i)?
+                    .read_cells_masked(window, window_size, size, e_resample_alg)
+            })
+            .collect()
+    }
+}
+
+impl CellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "gdal")))]
+    /// Create a single-band GDAL [`Dataset`] of `size` (width, height) at `path` using the
+    /// named `driver`, and write the buffer's cells into it.
+    ///
+    /// The band's data type is determined by [`Self::cell_type`]. Returns
+    /// `Err(UnsupportedCellTypeError)` for cell-types GDAL can't represent, and
+    /// `Err(LengthMismatch)` if `self.len()` is not `size.0 * size.1`.
+    pub fn create_dataset(
+        &self,
+        driver: &str,
+        path: &Path,
+        size: (usize, usize),
+    ) -> Result<Dataset> {
+        macro_rules! create {
+            ($( ($id:ident, $_p:ty) ),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => create_dataset(v.clone(), driver, path, size, None),)*
+                    #[allow(unreachable_patterns)]
+                    o => Err(Error::UnsupportedCellTypeError(o.cell_type().to_string())),
+                }
+            }
+        }
+        with_gdal_ct!(create)
+    }
+}
+
+impl MaskedCellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "gdal")))]
+    /// Create a single-band GDAL [`Dataset`] of `size` (width, height) at `path` using the
+    /// named `driver`, writing the buffer's cells into it with masked-out cells replaced
+    /// by `nodata`, which is also set as the band's no-data value.
+    ///
+    /// Returns `Err(NoDataConversionError)` if `nodata` can't be represented in the buffer's
+    /// cell-type. See also [`CellBuffer::create_dataset`].
+    pub fn create_dataset(
+        &self,
+        driver: &str,
+        path: &Path,
+        size: (usize, usize),
+        nodata: CellValue,
+    ) -> Result<Dataset> {
+        macro_rules! create {
+            ($( ($id:ident, $p:ty) ),*) => {
+                match self.cell_type() {
+                    $(CellType::$id => {
+                        let nd = <$p as NumCast>::from(nodata).ok_or_else(|| {
+                            Error::NoDataConversionError(nodata.to_f64().unwrap(), stringify!($p))
+                        })?;
+                        let data = self.clone().to_vec_with_nodata(NoData::new(nd))?;
+                        create_dataset(data, driver, path, size, nd.to_f64())
+                    })*
+                    #[allow(unreachable_patterns)]
+                    o => Err(Error::UnsupportedCellTypeError(o.to_string())),
+                }
+            }
+        }
+        with_gdal_ct!(create)
+    }
+}
+
+fn create_dataset<T: GdalType + Copy>(
+    data: Vec<T>,
+    driver: &str,
+    path: &Path,
+    size: (usize, usize),
+    nodata: Option<f64>,
+) -> Result<Dataset> {
+    if data.len() != size.0 * size.1 {
+        return Err(Error::LengthMismatch { lhs: data.len(), rhs: size.0 * size.1 });
+    }
+    let driver = DriverManager::get_driver_by_name(driver)?;
+    let ds = driver.create_with_band_type::<T, _>(path, size.0 as isize, size.1 as isize, 1)?;
+    {
+        let mut rb = ds.rasterband(1)?;
+        rb.set_no_data_value(nodata)?;
+        rb.write((0, 0), size, &Buffer::new(size, data))?;
+    }
+    Ok(ds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BufferOps;
+    use erased_cells_testkit::*;
+
+    #[test]
+    fn read_all_cells() -> TestResult {
+        let ds = Dataset::open(DATA_DIR.join("L8-Elkton-VA-B5-nd.tiff"))?;
+        let size = ds.raster_size();
+        let bufs = ds.read_all_cells((0, 0), size, size, None)?;
+        assert_eq!(bufs.len(), 1);
+        assert_eq!(bufs[0].len(), size.0 * size.1);
+
+        let masked = ds.read_all_cells_masked((0, 0), size, size, None)?;
+        assert_eq!(masked.len(), 1);
+        assert_eq!(masked[0].buffer(), &bufs[0]);
+        assert!(masked[0].counts().1 > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn create_dataset() -> TestResult {
+        let path = Path::new("/vsimem/create_dataset.tiff");
+        let buf = CellBuffer::fill_via(6, |i| i as i16 - 3);
+        let ds = buf.create_dataset("GTiff", path, (3, 2))?;
+        drop(ds);
+        let ds = Dataset::open(path)?;
+        assert_eq!(ds.raster_size(), (3, 2));
+        assert_eq!(
+            ds.read_all_cells((0, 0), (3, 2), (3, 2), None)?,
+            vec![buf.clone()]
+        );
+
+        assert!(buf.create_dataset("GTiff", path, (2, 2)).is_err());
+        let big = CellBuffer::fill_via(6, |i| i64::MAX - i as i64);
+        #[cfg(not(gdal_int64))]
+        assert!(big.create_dataset("MEM", Path::new(""), (3, 2)).is_err());
+        #[cfg(gdal_int64)]
+        {
+            let ds = big.create_dataset("MEM", Path::new(""), (3, 2))?;
+            assert_eq!(ds.read_all_cells((0, 0), (3, 2), (3, 2), None)?, vec![big]);
+        }
+        #[cfg(gdal_int8)]
+        {
+            let small = CellBuffer::fill_via(6, |i| -(i as i8));
+            let ds = small.create_dataset("MEM", Path::new(""), (3, 2))?;
+            assert_eq!(
+                ds.read_all_cells((0, 0), (3, 2), (3, 2), None)?,
+                vec![small]
+            );
+        }
+
+        let masked = MaskedCellBuffer::fill_with_mask_via(6, |i| (i as u8, i % 2 == 0));
+        let ds = masked.create_dataset("MEM", Path::new(""), (3, 2), 255u8.into())?;
+        assert_eq!(ds.rasterband(1)?.no_data_value(), Some(255.0));
+        let read = ds.read_all_cells_masked((0, 0), (3, 2), (3, 2), None)?;
+        assert_eq!(read[0].mask(), masked.mask());
+        assert!(masked
+            .create_dataset("MEM", Path::new(""), (3, 2), (-1i8).into())
+            .is_err());
+        Ok(())
+    }
+}
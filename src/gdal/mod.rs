@@ -3,14 +3,18 @@
 use crate::error::Result;
 use crate::{with_ct, CellEncoding, CellType, NoData};
 use gdal::raster::GdalDataType;
-use num_traits::ToPrimitive;
-use paste::paste;
+use num_traits::NumCast;
 
+mod dataset;
 mod rasterband;
+pub use dataset::*;
 pub use rasterband::*;
 
-// Note: Older versions of GDAL do not support Int8, Int64 and UInt64, so we have
-// a reduced set of cell types in this module.
+// Note: Older versions of GDAL do not support Int8 (< 3.7), Int64 and UInt64 (< 3.5), so
+// the set of cell types in this module depends on the `gdal_int8` and `gdal_int64` cfgs
+// emitted by `build.rs`. Matches over it keep a fallback arm for the remaining cell types,
+// which is unreachable when every type is supported (and `f16` is disabled).
+#[cfg(not(any(gdal_int8, gdal_int64)))]
 macro_rules! with_gdal_ct {
     ($callback:ident) => {
         $callback! {
@@ -24,6 +28,54 @@ macro_rules! with_gdal_ct {
         }
     };
 }
+#[cfg(all(gdal_int64, not(gdal_int8)))]
+macro_rules! with_gdal_ct {
+    ($callback:ident) => {
+        $callback! {
+            (UInt8, u8),
+            (UInt16, u16),
+            (UInt32, u32),
+            (UInt64, u64),
+            (Int16, i16),
+            (Int32, i32),
+            (Int64, i64),
+            (Float32, f32),
+            (Float64, f64)
+        }
+    };
+}
+#[cfg(all(gdal_int8, not(gdal_int64)))]
+macro_rules! with_gdal_ct {
+    ($callback:ident) => {
+        $callback! {
+            (UInt8, u8),
+            (UInt16, u16),
+            (UInt32, u32),
+            (Int8, i8),
+            (Int16, i16),
+            (Int32, i32),
+            (Float32, f32),
+            (Float64, f64)
+        }
+    };
+}
+#[cfg(all(gdal_int8, gdal_int64))]
+macro_rules! with_gdal_ct {
+    ($callback:ident) => {
+        $callback! {
+            (UInt8, u8),
+            (UInt16, u16),
+            (UInt32, u32),
+            (UInt64, u64),
+            (Int8, i8),
+            (Int16, i16),
+            (Int32, i32),
+            (Int64, i64),
+            (Float32, f32),
+            (Float64, f64)
+        }
+    };
+}
 pub(crate) use with_gdal_ct;
 
 /// Convert from [`GdalDataType`] to appropriate [`CellType`].
@@ -32,7 +84,7 @@ impl TryFrom<GdalDataType> for CellType {
 
     fn try_from(value: GdalDataType) -> Result<Self, Self::Error> {
         macro_rules! try_from {
-            ($( ($id:ident, $_p:ident) ),*) => {
+            ($( ($id:ident, $_p:ty) ),*) => {
                 match value {
                     $(GdalDataType::$id => Ok(CellType::$id),)*
                     o => Err(Self::Error::UnsupportedCellTypeError(o.to_string())),
@@ -43,6 +95,27 @@ impl TryFrom<GdalDataType> for CellType {
     }
 }
 
+/// Convert from [`CellType`] to appropriate [`GdalDataType`].
+///
+/// Returns `Err(UnsupportedCellTypeError)` for `Float16`, and for `Int8`, `Int64` and `UInt64`
+/// when the linked version of GDAL doesn't support them.
+impl TryFrom<CellType> for GdalDataType {
+    type Error = crate::error::Error;
+
+    fn try_from(value: CellType) -> Result<Self, Self::Error> {
+        macro_rules! try_from {
+            ($( ($id:ident, $_p:ty) ),*) => {
+                match value {
+                    $(CellType::$id => Ok(GdalDataType::$id),)*
+                    #[allow(unreachable_patterns)]
+                    o => Err(Self::Error::UnsupportedCellTypeError(o.to_string())),
+                }
+            }
+        }
+        with_gdal_ct!(try_from)
+    }
+}
+
 /// No-data conversion support.
 pub(crate) struct GdalND(Option<f64>);
 
@@ -51,19 +124,18 @@ impl<T: CellEncoding> TryFrom<GdalND> for NoData<T> {
 
     fn try_from(value: GdalND) -> std::result::Result<Self, Self::Error> {
         macro_rules! nd_convert {
-            ($( ($id:ident, $p:ident) ),*) => { paste! {
+            ($( ($id:ident, $p:ty) ),*) => {
                 match value.0 {
                     None => Ok(NoData::None),
                     Some(nd) => match T::cell_type() {
-                        $(CellType::$id => nd
-                            .[<to_ $p>]()
+                        $(CellType::$id => <$p as NumCast>::from(nd)
                             .and_then(T::static_cast)
                             .map(NoData::new)
                             .ok_or(Self::Error::NoDataConversionError(nd, stringify!($p)))
                         ,)*
                     }
                 }
-            }}
+            }
         }
         with_ct!(nd_convert)
     }
@@ -76,8 +148,28 @@ mod tests {
 
     #[test]
     fn gdal_enum() {
-        for dt in GdalDataType::iter().filter(|g| !format!("{g:?}").contains("Int64")) {
+        for dt in GdalDataType::iter() {
             let _ct: CellType = dt.try_into().unwrap();
         }
     }
+
+    #[test]
+    fn cell_type_round_trip() {
+        let mut supported = 0;
+        for ct in CellType::iter() {
+            match GdalDataType::try_from(ct) {
+                Ok(dt) => {
+                    supported += 1;
+                    assert_eq!(CellType::try_from(dt).unwrap(), ct);
+                }
+                Err(_) => assert!(
+                    (!cfg!(gdal_int8) && ct == CellType::Int8)
+                        || (!cfg!(gdal_int64) && matches!(ct, CellType::Int64 | CellType::UInt64))
+                        || (!ct.is_integral() && ct.size_of() == 2)
+                ),
+            }
+        }
+        let expected = 7 + 2 * cfg!(gdal_int64) as usize + cfg!(gdal_int8) as usize;
+        assert_eq!(supported, expected);
+    }
 }
@@ -1,6 +1,6 @@
 use super::with_gdal_ct;
 use crate::error::*;
-use crate::{gdal::GdalND, BufferOps, CellBuffer, CellType, MaskedCellBuffer};
+use crate::{gdal::GdalND, BufferOps, CellBuffer, CellType, Mask, MaskedCellBuffer};
 use gdal::raster::{RasterBand, ResampleAlg};
 
 /// Extension methods on [`RasterBand`]  to read/write [`CellBuffer`]s.
@@ -42,10 +42,30 @@ pub trait RasterBandEx {
         e_resample_alg: Option<ResampleAlg>,
     ) -> Result<CellBuffer>;
 
+    #[cfg_attr(docsrs, doc(cfg(feature = "gdal")))]
+    /// Read a [`CellBuffer`] from a GDAL [`RasterBand`], converting raw values to physical
+    /// values using the band's [`scale`][RasterBand::scale] and [`offset`][RasterBand::offset].
+    ///
+    /// The result is always `Float64`, computed as `raw * scale + offset` via
+    /// [`CellBuffer::scale_offset`]. A missing scale is treated as `1.0` and a missing
+    /// offset as `0.0`.
+    ///
+    /// See [`read_cells`][Self::read_cells] for a description of the arguments.
+    fn read_cells_scaled(
+        &self,
+        window: (isize, isize),
+        window_size: (usize, usize),
+        size: (usize, usize),
+        e_resample_alg: Option<ResampleAlg>,
+    ) -> Result<CellBuffer>;
+
     #[cfg_attr(docsrs, doc(cfg(feature = "gdal")))]
     /// Read a [`MaskedCellBuffer`] from a GDAL [`RasterBand`].
     ///
     /// Any no-data value setting will be used to construct the buffer's [`Mask`][crate::Mask].
+    /// If the band also has an explicit mask band (i.e. one not derived from the no-data value,
+    /// such as a per-dataset or alpha mask), it is read and combined with the no-data mask
+    /// via logical AND.
     ///
     /// # Arguments
     /// * `window` - the window position from top left
@@ -88,19 +108,30 @@ impl RasterBandEx for RasterBand<'_> {
     ) -> Result<CellBuffer> {
         let ct: CellType = self.band_type().try_into()?;
         macro_rules! read_cells {
-            ($( ($id:ident, $p:ident) ),*) => {
+            ($( ($id:ident, $p:ty) ),*) => {
                 match ct {
                     $(
                     CellType::$id => {
                         let v = self.read_as::<$p>(window, window_size, size, e_resample_alg)?;
                         Ok(CellBuffer::new(v.data))
                     }),*
+                    #[allow(unreachable_patterns)]
                     o => Err(Error::UnsupportedCellTypeError(o.to_string())),
                 }
             }
         }
         with_gdal_ct!(read_cells)
     }
+    fn read_cells_scaled(
+        &self,
+        window: (isize, isize),
+        window_size: (usize, usize),
+        size: (usize, usize),
+        e_resample_alg: Option<ResampleAlg>,
+    ) -> Result<CellBuffer> {
+        let buf = self.read_cells(window, window_size, size, e_resample_alg)?;
+        Ok(buf.scale_offset(self.scale().unwrap_or(1.0), self.offset().unwrap_or(0.0)))
+    }
     fn read_cells_masked(
         &self,
         window: (isize, isize),
@@ -112,16 +143,28 @@ impl RasterBandEx for RasterBand<'_> {
         let nd = GdalND(self.no_data_value());
 
         macro_rules! read_masked {
-            ($( ($id:ident, $p:ident) ),*) => {
+            ($( ($id:ident, $p:ty) ),*) => {
                 match buf {
                     $(
                     CellBuffer::$id(v) => Ok(MaskedCellBuffer::from_vec_with_nodata::<$p>(v, nd.try_into()?)),
                     )*
+                    #[allow(unreachable_patterns)]
                     o => Err(Error::UnsupportedCellTypeError(o.cell_type().to_string())),
                 }
             }
         }
-        with_gdal_ct!(read_masked)
+        let mut buf: MaskedCellBuffer = with_gdal_ct!(read_masked)?;
+
+        let flags = self.mask_flags()?;
+        if !flags.is_all_valid() && !flags.is_nodata() {
+            // Nearest-neighbor resampling keeps mask values binary.
+            let mask_band = self.open_mask_band()?;
+            let mask_data = mask_band.read_as::<u8>(window, window_size, size, None)?;
+            let band_mask = Mask::new(mask_data.data.into_iter().map(|m| m != 0).collect());
+            let mask = std::mem::take(buf.mask_mut());
+            *buf.mask_mut() = mask & band_mask;
+        }
+        Ok(buf)
     }
 }
 
@@ -162,6 +205,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn read_cells_scaled() -> TestResult {
+        use gdal::raster::Buffer;
+        use gdal::DriverManager;
+
+        let ds = DriverManager::get_driver_by_name("MEM")?
+            .create_with_band_type::<i16, _>("", 2, 2, 1)?;
+        let mut rb = ds.rasterband(1)?;
+        rb.write(
+            (0, 0),
+            (2, 2),
+            &Buffer::new((2, 2), vec![0i16, 10, -10, 200]),
+        )?;
+
+        // No scale/offset metadata leaves values unchanged, but still promotes to `Float64`.
+        let buf = rb.read_cells_scaled((0, 0), (2, 2), (2, 2), None)?;
+        assert_eq!(buf, CellBuffer::from_vec(vec![0.0, 10.0, -10.0, 200.0]));
+
+        rb.set_scale(0.1)?;
+        rb.set_offset(5.0)?;
+        let raw = rb.read_cells((0, 0), (2, 2), (2, 2), None)?;
+        let buf = rb.read_cells_scaled((0, 0), (2, 2), (2, 2), None)?;
+        assert_eq!(buf, raw.scale_offset(0.1, 5.0));
+        assert!(buf.approx_eq(&CellBuffer::from_vec(vec![5.0, 6.0, 4.0, 25.0]), 1e-9));
+        Ok(())
+    }
+
     #[test]
     fn read_cells_masked() -> TestResult {
         fn read(p: PathBuf) -> Result<MaskedCellBuffer> {
@@ -189,4 +259,59 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn summary_matches_gdal() -> TestResult {
+        for name in [
+            "L8-Elkton-VA-B4.tiff",
+            "L8-Elkton-VA-B5.tiff",
+            "L8-Elkton-VA-B5-nd.tiff",
+        ] {
+            let ds = Dataset::open(DATA_DIR.join(name))?;
+            let rb = ds.rasterband(1)?;
+            // Exact (non-approximate) statistics, computed even if not cached.
+            let (force, is_approx_ok) = (true, false);
+            let expected = rb.get_statistics(force, is_approx_ok)?.expect("statistics");
+            let buf = rb.read_cells_masked((0, 0), ds.raster_size(), ds.raster_size(), None)?;
+            let s = buf.summary();
+            let close = |l: f64, r: f64| (l - r).abs() <= 1e-6 * r.abs().max(1.0);
+            assert_eq!(s.count, buf.len(), "{name}");
+            assert_eq!(s.valid_count, buf.counts().0, "{name}");
+            assert!(close(s.min.to_f64().unwrap(), expected.min), "{name}");
+            assert!(close(s.max.to_f64().unwrap(), expected.max), "{name}");
+            assert!(close(s.mean, expected.mean), "{name}");
+            assert!(close(s.std_dev, expected.std_dev), "{name}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn read_cells_mask_band() -> TestResult {
+        use gdal::raster::Buffer;
+        use gdal::DriverManager;
+
+        let ds = DriverManager::get_driver_by_name("MEM")?
+            .create_with_band_type::<u16, _>("", 3, 2, 1)?;
+        let mut rb = ds.rasterband(1)?;
+        rb.set_no_data_value(Some(0.0))?;
+        rb.write(
+            (0, 0),
+            (3, 2),
+            &Buffer::new((3, 2), vec![0u16, 1, 2, 3, 4, 5]),
+        )?;
+        rb.create_mask_band(false)?;
+        let mut mb = rb.open_mask_band()?;
+        mb.write(
+            (0, 0),
+            (3, 2),
+            &Buffer::new((3, 2), vec![255u8, 255, 0, 255, 0, 255]),
+        )?;
+
+        let buf = rb.read_cells_masked((0, 0), (3, 2), (3, 2), None)?;
+        assert_eq!(
+            buf.mask(),
+            &Mask::new(vec![false, true, false, true, false, true])
+        );
+        Ok(())
+    }
 }
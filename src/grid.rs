@@ -0,0 +1,200 @@
+//! Two-dimensional views over [`CellBuffer`]s.
+
+use crate::error::{Error, Result};
+use crate::{BufferOps, CellBuffer, CellValue};
+
+/// A read-only, row-major 2-D view over a [`CellBuffer`].
+///
+/// Created via [`CellBuffer::as_2d`].
+///
+/// # Example
+///
+/// ```rust
+/// use erased_cells::{BufferOps, CellBuffer, CellValue};
+/// let buf = CellBuffer::fill_via(6, |i| i as u8);
+/// let grid = buf.as_2d(3).unwrap();
+/// assert_eq!(grid.dimensions(), (2, 3));
+/// assert_eq!(grid.get(1, 0), CellValue::UInt8(3));
+/// assert_eq!(grid.row(1), CellBuffer::from_vec(vec![3u8, 4, 5]));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Grid<'a> {
+    buffer: &'a CellBuffer,
+    cols: usize,
+}
+
+/// Treatment of kernel windows extending beyond the edges of a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgePolicy {
+    /// Only yield windows lying entirely within the grid.
+    Skip,
+    /// Yield a window centered on every cell, with out-of-bounds positions clamped to
+    /// the nearest edge cell.
+    Clamp,
+}
+
+impl CellBuffer {
+    /// Create a 2-D view of `self` with `cols` columns, interpreting cells in row-major order.
+    ///
+    /// Returns `Err(ValidationError)` if `cols` is zero or doesn't evenly divide `self.len()`.
+    pub fn as_2d(&self, cols: usize) -> Result<Grid<'_>> {
+        if cols == 0 || self.len() % cols != 0 {
+            return Err(Error::ValidationError(format!(
+                "buffer length {} is not a multiple of {cols} columns",
+                self.len()
+            )));
+        }
+        Ok(Grid { buffer: self, cols })
+    }
+}
+
+impl<'a> Grid<'a> {
+    /// Get the underlying buffer.
+    pub fn buffer(&self) -> &'a CellBuffer {
+        self.buffer
+    }
+
+    /// Get the `(rows, cols)` dimensions of the grid.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.buffer.len() / self.cols, self.cols)
+    }
+
+    /// Get the [`CellValue`] at `(row, col)`.
+    ///
+    /// # Panics
+    /// Will panic if `row` or `col` is out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> CellValue {
+        let (rows, cols) = self.dimensions();
+        assert!(
+            row < rows && col < cols,
+            "index ({row}, {col}) out of bounds for grid of {rows}x{cols}"
+        );
+        self.buffer.get(row * cols + col)
+    }
+
+    /// Create a new buffer containing a copy of the cells in row `r`.
+    ///
+    /// # Panics
+    /// Will panic if `r` is out of bounds.
+    pub fn row(&self, r: usize) -> CellBuffer {
+        let (rows, cols) = self.dimensions();
+        assert!(r < rows, "row {r} out of bounds for grid of {rows} rows");
+        self.buffer.slice(r * cols..(r + 1) * cols)
+    }
+
+    /// Iterate over `(rows, cols)` sized kernel windows, in row-major order, yielding each
+    /// window's cells flattened into a new buffer of the same [`CellType`][crate::CellType].
+    ///
+    /// With [`EdgePolicy::Skip`], a window is yielded for each position the kernel fits
+    /// entirely within the grid. With [`EdgePolicy::Clamp`], a window is yielded for each
+    /// cell, centered on it (offset by `kernel / 2` for even-sized kernels).
+    ///
+    /// # Example
+    /// ```rust
+    /// use erased_cells::{BufferOps, CellBuffer, EdgePolicy};
+    /// use num_traits::ToPrimitive;
+    /// // 3x3 mean filter.
+    /// let buf = CellBuffer::fill_via(16, |i| i as f32);
+    /// let grid = buf.as_2d(4).unwrap();
+    /// let means: Vec<f64> = grid
+    ///     .windows((3, 3), EdgePolicy::Skip)
+    ///     .map(|w| w.into_iter().map(|v| v.to_f64().unwrap()).sum::<f64>() / 9.0)
+    ///     .collect();
+    /// assert_eq!(means, vec![5.0, 6.0, 9.0, 10.0]);
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if either kernel dimension is zero.
+    pub fn windows(
+        &self,
+        kernel: (usize, usize),
+        edge: EdgePolicy,
+    ) -> impl Iterator<Item = CellBuffer> + 'a {
+        let (kr, kc) = kernel;
+        assert!(kr != 0 && kc != 0, "kernel dimensions must be non-zero");
+        let (rows, cols) = self.dimensions();
+        let buffer = self.buffer;
+        // Top-left position of each window, which may be negative when clamping.
+        let (row_starts, col_starts) = match edge {
+            EdgePolicy::Skip => (
+                0..(rows + 1).saturating_sub(kr) as isize,
+                0..(cols + 1).saturating_sub(kc) as isize,
+            ),
+            EdgePolicy::Clamp => (
+                -((kr / 2) as isize)..rows as isize - (kr / 2) as isize,
+                -((kc / 2) as isize)..cols as isize - (kc / 2) as isize,
+            ),
+        };
+        let clamp = |i: isize, len: usize| i.clamp(0, len as isize - 1) as usize;
+        row_starts
+            .flat_map(move |r| col_starts.clone().map(move |c| (r, c)))
+            .map(move |(r, c)| {
+                (0..kr as isize)
+                    .flat_map(|dr| (0..kc as isize).map(move |dc| (dr, dc)))
+                    .map(|(dr, dc)| buffer.get(clamp(r + dr, rows) * cols + clamp(c + dc, cols)))
+                    .collect()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufferOps, CellBuffer, CellValue, EdgePolicy};
+    use num_traits::ToPrimitive;
+
+    #[test]
+    fn grid() {
+        let buf = CellBuffer::fill_via(9, |i| i as i16);
+        let grid = buf.as_2d(3).unwrap();
+        assert_eq!(grid.dimensions(), (3, 3));
+        assert_eq!(grid.get(0, 0), CellValue::Int16(0));
+        assert_eq!(grid.get(1, 2), CellValue::Int16(5));
+        assert_eq!(grid.get(2, 1), CellValue::Int16(7));
+        assert_eq!(grid.row(2), CellBuffer::from_vec(vec![6i16, 7, 8]));
+        assert!(std::ptr::eq(grid.buffer(), &buf));
+
+        assert!(buf.as_2d(2).is_err());
+        assert!(buf.as_2d(0).is_err());
+        assert_eq!(buf.as_2d(9).unwrap().dimensions(), (1, 9));
+    }
+
+    #[test]
+    fn windows() {
+        let mean = |w: CellBuffer| {
+            w.into_iter().map(|v| v.to_f64().unwrap()).sum::<f64>() / w.len() as f64
+        };
+        let buf = CellBuffer::fill_via(20, |i| i as u8);
+        let grid = buf.as_2d(5).unwrap();
+
+        let skip: Vec<_> = grid.windows((3, 3), EdgePolicy::Skip).collect();
+        assert_eq!(skip.len(), 6);
+        assert_eq!(
+            skip[0],
+            CellBuffer::from_vec(vec![0u8, 1, 2, 5, 6, 7, 10, 11, 12])
+        );
+        let means: Vec<_> = skip.into_iter().map(mean).collect();
+        assert_eq!(means, vec![6.0, 7.0, 8.0, 11.0, 12.0, 13.0]);
+
+        let clamp: Vec<_> = grid.windows((3, 3), EdgePolicy::Clamp).collect();
+        assert_eq!(clamp.len(), 20);
+        assert_eq!(
+            clamp[0],
+            CellBuffer::from_vec(vec![0u8, 0, 1, 0, 0, 1, 5, 5, 6])
+        );
+        // Interior cells match the un-clamped windows.
+        assert_eq!(mean(clamp[6].clone()), 6.0);
+        assert_eq!(mean(clamp[13].clone()), 13.0);
+
+        assert_eq!(grid.windows((5, 1), EdgePolicy::Skip).count(), 0);
+        assert_eq!(grid.windows((1, 1), EdgePolicy::Clamp).count(), 20);
+        // Kernels larger than the grid are clamped.
+        assert_eq!(grid.windows((9, 9), EdgePolicy::Clamp).count(), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn grid_out_of_bounds() {
+        let buf = CellBuffer::fill_via(9, |i| i as i16);
+        buf.as_2d(3).unwrap().get(0, 3);
+    }
+}
@@ -18,7 +18,7 @@
 //! * [`Mask`]: A bit array used to store the associated validity of a [`CellValue`] within a [`MaskedCellBuffer`].
 //! * [`MaskedCellBuffer`]: The combination of  [`CellBuffer`] and [`Mask`].
 //! * [`NoData`]: Specification of a sentinel value for invalid data, used in converting
-//! between "`Vec<T: CellEncoding>`" and [`MaskedCellBuffer`].
+//!   between "`Vec<T: CellEncoding>`" and [`MaskedCellBuffer`].
 //!
 //! # Examples
 //!
@@ -35,18 +35,40 @@
 //! | `masked` | Enable the `MaskedCellBuffer` API              | `true`  |
 //! | `serde`  | Derive `serde` traits for core types           | `true`  |
 //! | `gdal`   | Enable `CellBuffer`s in `georust/gdal` API[^1] | `false` |
+//! | `rayon`  | Enable parallel operations via `rayon`         | `false` |
+//! | `approx` | Implement `approx` traits for core types       | `false` |
+//! | `bytemuck` | Enable zero-copy byte views via `bytemuck`   | `false` |
+//! | `ndarray` | Enable conversion to/from `ndarray` arrays    | `false` |
+//! | `arrow`  | Enable conversion to/from Apache Arrow arrays  | `false` |
+//! | `npy`    | Enable reading/writing NumPy `.npy` files      | `false` |
+//! | `f16`    | Enable the `Float16` cell-type via `half::f16`[^2] | `false` |
 //!
-//! [^1]: Note: `gdal` disables cell-types `UInt64`, `Int8`, and `Int64`
-//! to be backwards compatible with older versions of GDAL.
+//! [^1]: Note: with `gdal`, cell-types `UInt64` and `Int64` require GDAL >= 3.5, and `Int8`
+//! requires GDAL >= 3.7. Older versions of GDAL are detected at build time.
+//!
+//! [^2]: Note: `gdal` does not support `Float16`, as GDAL lacks a native 16-bit float type.
 
+#[cfg(feature = "approx")]
+mod approx;
+#[cfg(feature = "arrow")]
+mod arrow;
 mod buffer;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
 mod ctype;
 mod encoding;
 pub mod error;
 #[cfg(feature = "gdal")]
 mod gdal;
+mod grid;
 #[cfg(feature = "masked")]
 mod masked;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+#[cfg(feature = "npy")]
+mod npy;
+#[cfg(feature = "rayon")]
+mod par;
 mod value;
 
 pub use buffer::*;
@@ -54,6 +76,10 @@ pub use ctype::*;
 pub use encoding::*;
 #[cfg(feature = "gdal")]
 pub use gdal::*;
+pub use grid::*;
+#[cfg(feature = "f16")]
+#[cfg_attr(docsrs, doc(cfg(feature = "f16")))]
+pub use half::f16;
 #[cfg(feature = "masked")]
 pub use masked::*;
 use std::fmt::{Debug, Formatter};
@@ -63,25 +89,55 @@ pub use value::*;
 /// macro used to construct various implementations covering all [`CellType`]s.
 ///
 /// It calls the passed identifier as a macro with two parameters:
-/// * the cell type id (e.g. `UInt8`),
-/// * the cell type primitive (e.g. `u8`).
+/// * the cell type id (e.g. `UInt8`), an `ident`,
+/// * the cell type primitive (e.g. `u8`), a `ty`.
 ///
 /// # Example
 /// ```rust
 /// use erased_cells::{with_ct, CellType};
-/// fn primitive_name(ct: CellType) -> &'static str {
-///     macro_rules! primitive_name {
-///        ($(($id:ident, $p:ident)),*) => {
+/// fn size_of(ct: CellType) -> usize {
+///     macro_rules! size_of {
+///        ($(($id:ident, $p:ty)),*) => {
 ///             match ct {
-///                 $(CellType::$id => stringify!($p),)*
+///                 $(CellType::$id => std::mem::size_of::<$p>(),)*
+///                 // `CellType` is `#[non_exhaustive]`.
+///                 _ => unreachable!(),
 ///             }
 ///        };
 ///     }
-///     with_ct!(primitive_name)
+///     with_ct!(size_of)
 /// }
 ///
-/// assert_eq!(primitive_name(CellType::Float32), "f32");
+/// assert_eq!(size_of(CellType::Float32), 4);
 /// ```
+///
+/// When the `f16` feature is enabled, `(Float16, $crate::f16)` is also passed, so the
+/// primitive must be matched as a `ty` rather than an `ident`. Call sites don't need
+/// [`f16`](struct@crate::f16) in scope.
+#[cfg(not(feature = "f16"))]
+#[macro_export]
+macro_rules! with_ct {
+    ($callback:ident) => {
+        $callback! {
+            (UInt8, u8),
+            (UInt16, u16),
+            (UInt32, u32),
+            (UInt64, u64),
+            (Int8, i8),
+            (Int16, i16),
+            (Int32, i32),
+            (Int64, i64),
+            (Float32, f32),
+            (Float64, f64)
+        }
+    };
+}
+
+/// `with_ct` is a [callback style](https://danielkeep.github.io/tlborm/book/pat-callbacks.html)
+/// macro used to construct various implementations covering all [`CellType`]s.
+///
+/// See the non-`f16` variant for details.
+#[cfg(feature = "f16")]
 #[macro_export]
 macro_rules! with_ct {
     ($callback:ident) => {
@@ -94,12 +150,89 @@ macro_rules! with_ct {
             (Int16, i16),
             (Int32, i32),
             (Int64, i64),
+            (Float16, $crate::f16),
             (Float32, f32),
             (Float64, f64)
         }
     };
 }
 
+/// Construct a [`CellBuffer`] or [`MaskedCellBuffer`] from a literal list of values.
+///
+/// # Examples
+///
+/// Cell-type inferred from the values:
+/// ```rust
+/// use erased_cells::{cells, BufferOps, CellBuffer, CellType};
+/// let buf = cells![1u8, 2, 3];
+/// assert_eq!(buf, CellBuffer::from_vec(vec![1u8, 2, 3]));
+/// ```
+///
+/// Cell-type given by primitive annotation:
+/// ```rust
+/// use erased_cells::{cells, BufferOps, CellType};
+/// let buf = cells![f32: 1.0, 2.0];
+/// assert_eq!(buf.cell_type(), CellType::Float32);
+/// ```
+///
+/// `(value, mask)` pairs, constructing a [`MaskedCellBuffer`] (_requires `masked` feature_):
+/// ```rust
+/// # #[cfg(feature = "masked")] {
+/// use erased_cells::{cells, BufferOps, Mask};
+/// let buf = cells![(1u8, true), (2, false)];
+/// assert_eq!(buf.mask(), &Mask::new(vec![true, false]));
+/// let buf = cells![i16: (-1, true), (2, false)];
+/// assert_eq!(buf.get_masked(0), Some((-1i16).into()));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cells {
+    ($p:ident: $( ($v:expr, $m:expr) ),+ $(,)?) => {
+        $crate::__cells_masked!(
+            $crate::CellBuffer::from({
+                let v: ::std::vec::Vec<$p> = ::std::vec![$($v),+];
+                v
+            }),
+            ::std::vec![$($m),+]
+        )
+    };
+    ($( ($v:expr, $m:expr) ),+ $(,)?) => {
+        $crate::__cells_masked!(
+            $crate::CellBuffer::from(::std::vec![$($v),+]),
+            ::std::vec![$($m),+]
+        )
+    };
+    ($p:ident: $($v:expr),+ $(,)?) => {
+        $crate::CellBuffer::from({
+            let v: ::std::vec::Vec<$p> = ::std::vec![$($v),+];
+            v
+        })
+    };
+    ($($v:expr),+ $(,)?) => {
+        $crate::CellBuffer::from(::std::vec![$($v),+])
+    };
+}
+
+/// Implementation detail of [`cells!`] for `(value, mask)` pairs.
+#[cfg(feature = "masked")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cells_masked {
+    ($buf:expr, $mask:expr) => {
+        $crate::MaskedCellBuffer::new($buf, $crate::Mask::new($mask))
+    };
+}
+
+/// Implementation detail of [`cells!`] for `(value, mask)` pairs.
+#[cfg(not(feature = "masked"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cells_masked {
+    ($buf:expr, $mask:expr) => {
+        ::std::compile_error!("`cells!` with `(value, mask)` pairs requires the `masked` feature")
+    };
+}
+
 /// Operations common to buffers of [`CellValue`]s.
 pub trait BufferOps {
     /// Construct a [`CellBuffer`] from a `Vec<T>`.
@@ -155,11 +288,61 @@ pub trait BufferOps {
     where
         Self: Sized;
 
+    /// Convert `self` into a `Vec<T>`, saturating and rounding cells that don't fit in `T`
+    /// via [`CellValue::convert_clamped`]. Unlike [`Self::to_vec`], this never fails.
+    ///
+    /// Masked buffers convert all cells, ignoring the mask.
+    fn to_vec_lossy<T: CellEncoding>(self) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        (0..self.len())
+            .map(|i| T::from_cell_value(self.get(i).convert_clamped(T::cell_type())).unwrap())
+            .collect()
+    }
+
     /// Compute the minimum and maximum values the buffer.
     fn min_max(&self) -> (CellValue, CellValue);
 
     /// Convert `self` into a `Vec<T>`.
     fn to_vec<T: CellEncoding>(self) -> error::Result<Vec<T>>;
+
+    /// Accumulate cells in order by applying `f` to the accumulator and each cell,
+    /// starting from `init`.
+    ///
+    /// Masked buffers consider only valid cells.
+    fn fold<B, F: Fn(B, CellValue) -> B>(&self, init: B, f: F) -> B {
+        (0..self.len()).fold(init, |acc, i| f(acc, self.get(i)))
+    }
+
+    /// Reduce cells to a single value by repeatedly applying `f`, using the first cell as
+    /// the initial accumulator. Returns `None` if there are no cells.
+    ///
+    /// Masked buffers consider only valid cells.
+    fn reduce<F: Fn(CellValue, CellValue) -> CellValue>(&self, f: F) -> Option<CellValue> {
+        self.fold(None, |acc, v| Some(acc.map_or(v, |a| f(a, v))))
+    }
+
+    /// Count the cells for which `f` returns `true`.
+    ///
+    /// Masked buffers consider only valid cells.
+    fn count_where<F: Fn(CellValue) -> bool>(&self, f: F) -> usize {
+        (0..self.len()).filter(|i| f(self.get(*i))).count()
+    }
+
+    /// Find the index of the first cell for which `f` returns `true`.
+    ///
+    /// Masked buffers consider only valid cells.
+    fn position<F: Fn(CellValue) -> bool>(&self, f: F) -> Option<usize> {
+        (0..self.len()).position(|i| f(self.get(i)))
+    }
+
+    /// Find the first cell for which `f` returns `true`.
+    ///
+    /// Masked buffers consider only valid cells.
+    fn find<F: Fn(CellValue) -> bool>(&self, f: F) -> Option<CellValue> {
+        self.position(f).map(|i| self.get(i))
+    }
 }
 
 /// Newtype wrapper for debug rendering utility.
@@ -1,9 +1,12 @@
+use crate::error::{Error, Result};
 use crate::Elided;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
-use std::ops::{BitAnd, BitOr, Index, IndexMut, Not};
+use std::iter::Copied;
+use std::ops::{BitAnd, BitOr, BitXor, Bound, Index, IndexMut, Not, RangeBounds};
+use std::slice::Iter;
 use std::vec::IntoIter;
 
 /// Encodes the bit-mask for [`MaskedCellBuffer`][super::MaskedCellBuffer]
@@ -58,6 +61,50 @@ impl Mask {
         self.0[index]
     }
 
+    /// Swap the mask values at positions `a` and `b`.
+    ///
+    /// # Panics
+    /// Will panic if `a` or `b` are out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.0.swap(a, b);
+    }
+
+    /// Append `value` to the end of the mask.
+    pub fn push(&mut self, value: bool) {
+        self.0.push(value);
+    }
+
+    /// Resize the mask in-place to `new_len`, either truncating, or filling new entries with `value`.
+    pub fn resize(&mut self, new_len: usize, value: bool) {
+        self.0.resize(new_len, value);
+    }
+
+    /// Create a new mask containing a copy of the values within `range`.
+    ///
+    /// # Panics
+    /// Will panic if `range` is out of bounds, as with standard slicing.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let bounds: (Bound<usize>, Bound<usize>) =
+            (range.start_bound().cloned(), range.end_bound().cloned());
+        Self(self.0[bounds].to_vec())
+    }
+
+    /// Iterate over successive sub-masks of `size` values.
+    ///
+    /// The last chunk will be shorter than `size` if `size` does not evenly divide `self.len()`.
+    ///
+    /// # Panics
+    /// Will panic if `size` is zero.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Mask> + '_ {
+        assert!(size != 0, "chunk size must be non-zero");
+        self.0.chunks(size).map(|c| Self(c.to_vec()))
+    }
+
+    /// Gets an iterator over values in mask, in sequence.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.into_iter()
+    }
+
     /// Gets an mutable iterator over values in mask, in sequence.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &'_ mut bool> {
         self.0.iter_mut()
@@ -68,6 +115,36 @@ impl Mask {
         self.0.iter().all(|b| *b == value)
     }
 
+    /// Pack mask values into bytes, 8 per byte, least-significant bit first.
+    ///
+    /// The result has length `self.len().div_ceil(8)`, with any unused high bits of the last
+    /// byte set to `0`. As the length is not encoded, it must be stored alongside the bytes
+    /// for use with [`Self::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0
+            .chunks(8)
+            .map(|c| {
+                c.iter()
+                    .enumerate()
+                    .fold(0u8, |acc, (i, b)| acc | ((*b as u8) << i))
+            })
+            .collect()
+    }
+
+    /// Unpack `len` mask values from bytes packed by [`Self::to_bytes`].
+    ///
+    /// Returns `Err(LengthMismatch)` if `bytes.len() != len.div_ceil(8)`.
+    pub fn from_bytes(len: usize, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != len.div_ceil(8) {
+            return Err(Error::LengthMismatch { lhs: bytes.len(), rhs: len.div_ceil(8) });
+        }
+        Ok(Self(
+            (0..len)
+                .map(|i| bytes[i / 8] & (1 << (i % 8)) != 0)
+                .collect(),
+        ))
+    }
+
     /// Returns a tuple of representing counts of `(data, nodata)`.
     pub fn counts(&self) -> (usize, usize) {
         self.0.iter().fold((0, 0), |(data, nodata), m| {
@@ -80,6 +157,76 @@ impl Mask {
     }
 }
 
+/// Wrapper around [`Mask`] serialized as packed bits encoded in base64, along
+/// with the mask length.
+///
+/// The default `Mask` serialization is a sequence of booleans, which is
+/// verbose in text formats such as JSON.
+///
+/// # Example
+///
+/// ```rust
+/// use erased_cells::{CompactMask, Mask};
+/// let mask = Mask::fill_via(1000, |i| i % 3 == 0);
+/// let json = serde_json::to_string(&CompactMask(mask.clone())).unwrap();
+/// let CompactMask(parsed) = serde_json::from_str(&json).unwrap();
+/// assert_eq!(parsed, mask);
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompactMask(pub Mask);
+
+#[cfg(feature = "serde")]
+mod compact {
+    use super::{CompactMask, Mask};
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialized form of [`CompactMask`].
+    #[derive(Serialize, Deserialize)]
+    struct Repr {
+        len: usize,
+        bits: String,
+    }
+
+    impl Serialize for CompactMask {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let repr = Repr {
+                len: self.0.len(),
+                bits: STANDARD.encode(self.0.to_bytes()),
+            };
+            repr.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CompactMask {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let Repr { len, bits } = Repr::deserialize(deserializer)?;
+            let bytes = STANDARD.decode(bits).map_err(D::Error::custom)?;
+            Mask::from_bytes(len, &bytes)
+                .map(CompactMask)
+                .map_err(D::Error::custom)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Mask> for CompactMask {
+    fn from(value: Mask) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<CompactMask> for Mask {
+    fn from(value: CompactMask) -> Self {
+        value.0
+    }
+}
+
 impl Extend<bool> for Mask {
     fn extend<T: IntoIterator<Item = bool>>(&mut self, iter: T) {
         self.0.extend(iter)
@@ -115,53 +262,52 @@ impl Not for &Mask {
     }
 }
 
-impl BitAnd for Mask {
-    type Output = Self;
-    fn bitand(mut self, rhs: Self) -> Self::Output {
-        self.0
-            .iter_mut()
-            .zip(rhs.0.iter())
-            .for_each(|(l, r)| *l &= *r);
-        self
-    }
-}
+/// Element-wise binary operators over [`Mask`]s.
+///
+/// # Panics
+/// Will panic if the operands are not the same length.
+macro_rules! mask_bin_op {
+    ($trt:ident, $mth:ident, $op:tt, $op_assign:tt) => {
+        impl $trt for Mask {
+            type Output = Self;
+            fn $mth(mut self, rhs: Self) -> Self::Output {
+                assert_len_eq(&self, &rhs);
+                self.0
+                    .iter_mut()
+                    .zip(rhs.0.iter())
+                    .for_each(|(l, r)| *l $op_assign *r);
+                self
+            }
+        }
 
-impl BitAnd for &Mask {
-    type Output = Mask;
-    fn bitand(self, rhs: Self) -> Self::Output {
-        Mask(
-            self.0
-                .iter()
-                .zip(rhs.0.iter())
-                .map(|(l, r)| *l & *r)
-                .collect(),
-        )
-    }
+        impl $trt for &Mask {
+            type Output = Mask;
+            fn $mth(self, rhs: Self) -> Self::Output {
+                assert_len_eq(self, rhs);
+                Mask(
+                    self.0
+                        .iter()
+                        .zip(rhs.0.iter())
+                        .map(|(l, r)| *l $op *r)
+                        .collect(),
+                )
+            }
+        }
+    };
 }
+mask_bin_op!(BitAnd, bitand, &, &=);
+mask_bin_op!(BitOr, bitor, |, |=);
+mask_bin_op!(BitXor, bitxor, ^, ^=);
 
-impl BitOr for Mask {
-    type Output = Self;
-    fn bitor(mut self, rhs: Self) -> Self::Output {
-        self.0
-            .iter_mut()
-            .zip(rhs.0.iter())
-            .for_each(|(l, r)| *l |= *r);
-        self
-    }
+#[inline]
+fn assert_len_eq(lhs: &Mask, rhs: &Mask) {
+    assert_eq!(
+        lhs.len(),
+        rhs.len(),
+        "Masks must have the same length to be combined."
+    );
 }
 
-impl BitOr for &Mask {
-    type Output = Mask;
-    fn bitor(self, rhs: Self) -> Self::Output {
-        Mask(
-            self.0
-                .iter()
-                .zip(rhs.0.iter())
-                .map(|(l, r)| *l | *r)
-                .collect(),
-        )
-    }
-}
 impl Debug for Mask {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("Mask({:?})", Elided(&self.0)))
@@ -176,8 +322,23 @@ impl IntoIterator for Mask {
     }
 }
 
+impl<'a> IntoIterator for &'a Mask {
+    type Item = bool;
+    type IntoIter = Copied<Iter<'a, bool>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().copied()
+    }
+}
+
+impl FromIterator<bool> for Mask {
+    fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::Mask;
 
     #[test]
@@ -198,6 +359,28 @@ mod tests {
         assert_eq!(m, Mask::new(vec![false, false, true]));
     }
 
+    #[test]
+    fn iterate() {
+        let m: Mask = (0..5).map(|i| i % 2 == 0).collect();
+        assert_eq!(m, Mask::fill_via(5, |i| i % 2 == 0));
+        assert_eq!(m.iter().filter(|b| *b).count(), 3);
+        let mut n = 0;
+        for b in &m {
+            assert_eq!(b, n % 2 == 0);
+            n += 1;
+        }
+        assert_eq!(n, m.len());
+        assert_eq!(m.iter().collect::<Mask>(), m);
+        assert!(Mask::from_iter([]).is_empty());
+    }
+
+    #[test]
+    fn slice() {
+        let m = Mask::fill_via(5, |i| i % 2 == 0);
+        assert_eq!(m.slice(..), m);
+        assert_eq!(m.slice(1..3), Mask::new(vec![false, true]));
+    }
+
     #[test]
     fn not() {
         let t = Mask::fill(4, true);
@@ -240,4 +423,65 @@ mod tests {
         // consuming
         assert!((l | r).all(true));
     }
+
+    #[test]
+    fn xor() {
+        let l = Mask::fill_via(4, |i| i % 2 == 0);
+        let r = Mask::new(vec![true, true, false, false]);
+        let expected = Mask::new(vec![false, true, true, false]);
+        // non-consuming
+        assert_eq!(&l ^ &r, expected);
+        // consuming
+        assert_eq!(l ^ r, expected);
+    }
+
+    #[test]
+    fn bytes() {
+        let values = vec![true, false, true, true, false, false, false, false, true];
+        let mask = Mask::new(values);
+        let packed = mask.to_bytes();
+        assert_eq!(packed, vec![0b1101, 0b1]);
+        assert_eq!(Mask::from_bytes(9, &packed).unwrap(), mask);
+        assert!(matches!(
+            Mask::from_bytes(17, &packed),
+            Err(Error::LengthMismatch { lhs: 2, rhs: 3 })
+        ));
+        assert!(Mask::from_bytes(8, &packed).is_err());
+
+        for len in [0, 7, 8, 9] {
+            let mask = Mask::fill_via(len, |i| i % 3 != 1);
+            let packed = mask.to_bytes();
+            assert_eq!(packed.len(), len.div_ceil(8));
+            assert_eq!(Mask::from_bytes(len, &packed).unwrap(), mask, "{len}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn compact_serde() {
+        use crate::CompactMask;
+        let mask = Mask::fill_via(1000, |i| i % 3 == 0);
+        let compact = serde_json::to_string(&CompactMask(mask.clone())).unwrap();
+        let verbose = serde_json::to_string(&mask).unwrap();
+        assert!(compact.len() * 10 < verbose.len(), "{compact}");
+
+        let CompactMask(parsed) = serde_json::from_str(&compact).unwrap();
+        assert_eq!(parsed, mask);
+
+        let empty: CompactMask = serde_json::from_str(r#"{"len":0,"bits":""}"#).unwrap();
+        assert!(empty.0.is_empty());
+        assert!(serde_json::from_str::<CompactMask>(r#"{"len":9,"bits":"AQ=="}"#).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn length_mismatch() {
+        let _ = Mask::fill(3, true) & Mask::fill(4, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn length_mismatch_borrowed() {
+        let _ = &Mask::fill(3, true) | &Mask::fill(4, true);
+    }
 }
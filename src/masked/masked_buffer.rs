@@ -1,7 +1,14 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Formatter};
-
-use crate::masked::nodata::IsNodata;
-use crate::{BufferOps, CellBuffer, CellEncoding, CellType, CellValue, Mask, NoData};
+use std::ops::RangeBounds;
+
+use crate::buffer::{arg_by, dot, entropy_per_class, pearson, summary, value_counts};
+use crate::error::Error;
+use crate::{
+    with_ct, BufferOps, BufferStats, CellBuffer, CellEncoding, CellType, CellValue, Mask, NoData,
+    NoDataSet,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -45,6 +52,7 @@ impl MaskedCellBuffer {
     ///
     /// # Panics
     /// Will panics if `buffer` and `mask` are not the same length.
+    /// See [`CellBuffer::with_mask`] for a non-panicking alternative.
     pub fn new(buffer: CellBuffer, mask: Mask) -> Self {
         assert_eq!(
             buffer.len(),
@@ -60,11 +68,27 @@ impl MaskedCellBuffer {
     ///
     /// Use [`Self::from_vec`]
     pub fn from_vec_with_nodata<T: CellEncoding>(data: Vec<T>, nodata: NoData<T>) -> Self {
+        Self::from_vec_masking(data, |v| nodata.is(v))
+    }
+
+    /// Constructs a `MaskedCellBuffer` from a `Vec<CellEncoding>`, specifying a set of
+    /// no-data values.
+    ///
+    /// Mask value will be `false` when associated cell matches any of the values in `nodata`.
+    pub fn from_vec_with_nodata_set<T: CellEncoding>(data: Vec<T>, nodata: &NoDataSet<T>) -> Self {
+        Self::from_vec_masking(data, |v| nodata.is(v))
+    }
+
+    fn from_vec_masking<T, F>(data: Vec<T>, is_nodata: F) -> Self
+    where
+        T: CellEncoding,
+        F: Fn(&CellValue) -> bool,
+    {
         let mut mask = Mask::fill(data.len(), true);
         let buf = CellBuffer::from_vec(data);
 
         buf.into_iter().zip(mask.iter_mut()).for_each(|(v, m)| {
-            *m = !v.is(nodata);
+            *m = !is_nodata(&v);
         });
 
         Self::new(buf, mask)
@@ -94,6 +118,75 @@ impl MaskedCellBuffer {
         &mut self.1
     }
 
+    /// Replace the mask with `mask`.
+    ///
+    /// Returns `Err(LengthMismatch)`, leaving `self` unchanged, if `mask` is not the same
+    /// length as the buffer.
+    pub fn set_mask(&mut self, mask: Mask) -> crate::error::Result<()> {
+        if mask.len() != self.buffer().len() {
+            return Err(Error::LengthMismatch {
+                lhs: self.buffer().len(),
+                rhs: mask.len(),
+            });
+        }
+        self.1 = mask;
+        Ok(())
+    }
+
+    /// Create a new buffer containing a copy of the cells and mask values within `range`.
+    ///
+    /// # Panics
+    /// Will panic if `range` is out of bounds, as with standard slicing.
+    pub fn slice<R: RangeBounds<usize> + Clone>(&self, range: R) -> Self {
+        Self::new(self.buffer().slice(range.clone()), self.mask().slice(range))
+    }
+
+    /// Divide the buffer and mask into two copies at index `mid`.
+    ///
+    /// # Panics
+    /// Will panic if `mid > self.len()`, as with [`slice::split_at`].
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        let (lb, rb) = self.buffer().split_at(mid);
+        (
+            Self::new(lb, self.mask().slice(..mid)),
+            Self::new(rb, self.mask().slice(mid..)),
+        )
+    }
+
+    /// Iterate over successive sub-buffers of `size` cells, chunking the buffer and mask together.
+    ///
+    /// The last chunk will be shorter than `size` if `size` does not evenly divide `self.len()`.
+    ///
+    /// # Panics
+    /// Will panic if `size` is zero.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = MaskedCellBuffer> + '_ {
+        self.buffer()
+            .chunks(size)
+            .zip(self.mask().chunks(size))
+            .map(|(b, m)| Self::new(b, m))
+    }
+
+    /// Check the buffer for internal consistency, as a debugging aid when the buffer
+    /// or mask have been manipulated through [`Self::buffer_mut`], [`Self::mask_mut`], FFI,
+    /// or other unchecked means.
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffer and mask lengths differ, or
+    /// `Err(ValidationError)` if [`CellBuffer::validate`] fails.
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.buffer().len() != self.mask().len() {
+            return Err(Error::LengthMismatch {
+                lhs: self.buffer().len(),
+                rhs: self.mask().len(),
+            });
+        }
+        self.buffer().validate()
+    }
+
+    /// Decompose `self` into its [`CellBuffer`] and [`Mask`].
+    pub fn into_buffer_and_mask(self) -> (CellBuffer, Mask) {
+        (self.0, self.1)
+    }
+
     /// Get a buffer value at position `index` with mask evaluated.
     ///
     /// Returns `Some(CellValue)` if mask at `index` is `true`, `None` otherwise.
@@ -128,19 +221,267 @@ impl MaskedCellBuffer {
         Ok(())
     }
 
+    /// Swap the cells and mask values at positions `a` and `b`.
+    ///
+    /// # Panics
+    /// Will panic if `a` or `b` are out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.buffer_mut().swap(a, b);
+        self.mask_mut().swap(a, b);
+    }
+
+    /// Append `value` and its associated `mask` value to the end of the buffer.
+    ///
+    /// Returns `Err(NarrowingError)` if `value` cannot be converted to
+    /// `self.cell_type()` without data loss (e.g. overflow).
+    pub fn push(&mut self, value: CellValue, mask: bool) -> crate::error::Result<()> {
+        self.buffer_mut().push(value)?;
+        self.mask_mut().push(mask);
+        Ok(())
+    }
+
+    /// Resize the buffer in-place to `new_len`, either truncating, or filling new cells
+    /// with `value` and new mask entries with `true`.
+    ///
+    /// Returns `Err(NarrowingError)` if `value` cannot be converted to
+    /// `self.cell_type()` without data loss (e.g. overflow).
+    pub fn resize(&mut self, new_len: usize, value: CellValue) -> crate::error::Result<()> {
+        self.buffer_mut().resize(new_len, value)?;
+        self.mask_mut().resize(new_len, true);
+        Ok(())
+    }
+
+    /// Narrow the mask to cells for which `pred` also returns `true`.
+    ///
+    /// `pred` is only called on valid cells, so cells already masked out stay masked out.
+    pub fn and_where<F: Fn(CellValue) -> bool>(&mut self, pred: F) {
+        let Self(buffer, mask) = self;
+        for (i, m) in mask.iter_mut().enumerate() {
+            *m = *m && pred(buffer.get(i));
+        }
+    }
+
+    /// Element-wise division of `self` by `rhs`, as with [`Self::try_div`], additionally
+    /// masking out cells where the result is non-finite (e.g. division by `0`).
+    ///
+    /// The `/` operator and [`Self::try_div`] leave such `NaN` and infinite results valid.
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffers differ in length.
+    pub fn div_masking_nonfinite(&self, rhs: &MaskedCellBuffer) -> crate::error::Result<Self> {
+        let mut r = self.try_div(rhs)?;
+        r.and_where(|v| v.get::<f64>().is_ok_and(f64::is_finite));
+        Ok(r)
+    }
+
     /// Returns a tuple of representing counts of `(data, nodata)`.
     pub fn counts(&self) -> (usize, usize) {
         self.mask().counts()
     }
 
+    /// Count the number of occurrences of each distinct valid value in the buffer.
+    pub fn value_counts(&self) -> BTreeMap<CellValue, usize> {
+        value_counts(self.into_iter().filter_map(|(v, m)| m.then_some(v)))
+    }
+
+    /// Count the valid cells within the inclusive range `[min, max]`, comparing after unifying
+    /// cell-types.
+    pub fn count_in_range(&self, min: CellValue, max: CellValue) -> usize {
+        self.into_iter()
+            .filter(|(v, m)| *m && (min..=max).contains(v))
+            .count()
+    }
+
+    /// Count the valid cells outside the inclusive range `[min, max]`.
+    ///
+    /// See [`Self::count_in_range`].
+    pub fn count_out_of_range(&self, min: CellValue, max: CellValue) -> usize {
+        self.mask().counts().0 - self.count_in_range(min, max)
+    }
+
+    /// Compute the Shannon entropy, in bits, of the distribution of valid values in the buffer.
+    ///
+    /// Returns `0.0` if there are no valid values.
+    pub fn shannon_entropy(&self) -> f64 {
+        entropy_per_class(&self.value_counts()).values().sum()
+    }
+
+    /// Compute each distinct valid value's contribution (`-p log₂ p`) to the
+    /// [Shannon entropy][Self::shannon_entropy] of the buffer.
+    pub fn entropy_per_class(&self) -> BTreeMap<CellValue, f64> {
+        entropy_per_class(&self.value_counts())
+    }
+
+    /// Get the index of the first minimum valid value, or `None` if there are no valid cells.
+    ///
+    /// See [`CellBuffer::argmin`].
+    pub fn argmin(&self) -> Option<usize> {
+        arg_by(self.valid_cells(), Ordering::Less, Ord::cmp)
+    }
+
+    /// Get the index of the first maximum valid value, or `None` if there are no valid cells.
+    ///
+    /// See [`CellBuffer::argmax`].
+    pub fn argmax(&self) -> Option<usize> {
+        arg_by(self.valid_cells(), Ordering::Greater, Ord::cmp)
+    }
+
+    /// Iterate over the index and value of each valid cell.
+    fn valid_cells(&self) -> impl Iterator<Item = (usize, CellValue)> + '_ {
+        self.into_iter()
+            .enumerate()
+            .filter_map(|(i, (v, m))| m.then_some((i, v)))
+    }
+
+    /// Compute summary statistics over the valid cells.
+    ///
+    /// `count` is the total number of cells, while `valid_count` and all other
+    /// statistics exclude masked-out cells. See [`BufferStats`].
+    pub fn summary(&self) -> BufferStats {
+        summary(&self.compress(), self.len())
+    }
+
+    /// Compute the dot product of `self` and `other`, using only positions valid in both.
+    ///
+    /// See [`CellBuffer::dot`]. Returns `Err(LengthMismatch)` if the buffers are not the
+    /// same length.
+    pub fn dot(&self, other: &MaskedCellBuffer) -> crate::error::Result<CellValue> {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: other.len() });
+        }
+        let pairs = self
+            .into_iter()
+            .zip(other)
+            .filter_map(|((x, xm), (y, ym))| (xm && ym).then_some((x, y)));
+        Ok(dot(self.cell_type().union(other.cell_type()), pairs))
+    }
+
+    /// Compute the Pearson correlation coefficient between `self` and `other`, using only
+    /// positions valid in both.
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffers are not the same length. The
+    /// result is `NaN` if either buffer has zero variance over the valid positions.
+    pub fn pearson(&self, other: &MaskedCellBuffer) -> crate::error::Result<f64> {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: other.len() });
+        }
+        let pairs = self
+            .into_iter()
+            .zip(other)
+            .filter_map(|((x, xm), (y, ym))| (xm && ym).then_some((x, y)));
+        Ok(pearson(pairs))
+    }
+
+    /// Determine if `self` and `other` have equal masks, and all valid cells are equal
+    /// within `epsilon`.
+    ///
+    /// See [`CellBuffer::approx_eq`].
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.mask() == other.mask() && self.compress().approx_eq(&other.compress(), epsilon)
+    }
+
+    /// Combine `self` and `other` element-wise with `f`, with the resulting mask being the
+    /// logical AND of both masks.
+    ///
+    /// See [`CellBuffer::zip_with`].
+    pub fn zip_with<F>(&self, other: &MaskedCellBuffer, f: F) -> crate::error::Result<Self>
+    where
+        F: Fn(CellValue, CellValue) -> CellValue,
+    {
+        let buffer = self.buffer().zip_with(other.buffer(), f)?;
+        Ok(Self::new(buffer, self.mask() & other.mask()))
+    }
+
+    /// Create a new buffer of the same [`CellType`] containing only the valid cells for which
+    /// `f` returns `true`.
+    ///
+    /// Since every retained cell is valid, the result is returned as a plain [`CellBuffer`].
+    pub fn filter_valid<F: Fn(CellValue) -> bool>(&self, f: F) -> CellBuffer {
+        macro_rules! filter_valid {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self.buffer() {
+                    $(CellBuffer::$id(v) => CellBuffer::$id(
+                        v.iter()
+                            .enumerate()
+                            .filter(|(i, x)| self.mask().get(*i) && f((**x).into()))
+                            .map(|(_, x)| *x)
+                            .collect()
+                    ),)*
+                }
+            };
+        }
+        with_ct!(filter_valid)
+    }
+
+    /// Create a new buffer containing only the valid cells, in order.
+    ///
+    /// The result has length `self.counts().0`. See [`Self::expand`] for the inverse.
+    pub fn compress(&self) -> CellBuffer {
+        self.filter_valid(|_| true)
+    }
+
+    /// Scatter `values` into the valid positions of `mask`, filling invalid positions with
+    /// `fill`. This is the inverse of [`Self::compress`].
+    ///
+    /// The result has the cell-type of `values`. Returns `Err(LengthMismatch)` if the
+    /// number of valid positions in `mask` differs from `values.len()`, or
+    /// `Err(NarrowingError)` if `fill` doesn't fit in the cell-type of `values`.
+    pub fn expand(values: &CellBuffer, mask: &Mask, fill: CellValue) -> crate::error::Result<Self> {
+        let valid = mask.counts().0;
+        if valid != values.len() {
+            return Err(Error::LengthMismatch { lhs: values.len(), rhs: valid });
+        }
+        let mut buffer = CellBuffer::with_defaults(mask.len(), values.cell_type());
+        let mut next = values.into_iter();
+        for i in 0..mask.len() {
+            // `unwrap` should be ok as the valid count has been checked.
+            let value = if mask.get(i) {
+                next.next().unwrap()
+            } else {
+                fill
+            };
+            buffer.put(i, value)?;
+        }
+        Ok(Self::new(buffer, mask.clone()))
+    }
+
+    /// Render each cell as a `String`, with invalid cells rendered as the empty string.
+    ///
+    /// See also: [`Self::to_string_vec_with`].
+    pub fn to_string_vec(&self) -> Vec<String> {
+        self.to_string_vec_with("")
+    }
+
+    /// Render each cell as a `String`, with invalid cells rendered as `nodata`.
+    pub fn to_string_vec_with(&self, nodata: &str) -> Vec<String> {
+        self.into_iter()
+            .map(|(v, m)| if m { v.to_string() } else { nodata.to_string() })
+            .collect()
+    }
+
     /// Convert `self` into a `Vec<T>`, replacing values where the mask is `0` to `no_data.value()`
     pub fn to_vec_with_nodata<T: CellEncoding>(
         self,
         no_data: NoData<T>,
+    ) -> crate::error::Result<Vec<T>> {
+        self.into_vec_replacing(no_data.value())
+    }
+
+    /// Convert `self` into a `Vec<T>`, replacing values where the mask is `0` with the first
+    /// value in `no_data`.
+    pub fn to_vec_with_nodata_set<T: CellEncoding>(
+        self,
+        no_data: &NoDataSet<T>,
+    ) -> crate::error::Result<Vec<T>> {
+        self.into_vec_replacing(no_data.value())
+    }
+
+    fn into_vec_replacing<T: CellEncoding>(
+        self,
+        no_data: Option<T>,
     ) -> crate::error::Result<Vec<T>> {
         let Self(buf, mask) = self;
         let out = buf.to_vec::<T>()?;
-        if let Some(no_data) = no_data.value() {
+        if let Some(no_data) = no_data {
             Ok(out
                 .into_iter()
                 .zip(mask)
@@ -222,6 +563,27 @@ impl BufferOps for MaskedCellBuffer {
     fn to_vec<T: CellEncoding>(self) -> crate::error::Result<Vec<T>> {
         self.0.to_vec()
     }
+
+    fn fold<B, F: Fn(B, CellValue) -> B>(&self, init: B, f: F) -> B {
+        self.into_iter()
+            .filter(|(_, m)| *m)
+            .fold(init, |acc, (v, _)| f(acc, v))
+    }
+
+    fn count_where<F: Fn(CellValue) -> bool>(&self, f: F) -> usize {
+        self.into_iter().filter(|(v, m)| *m && f(*v)).count()
+    }
+
+    fn position<F: Fn(CellValue) -> bool>(&self, f: F) -> Option<usize> {
+        self.into_iter().position(|(v, m)| m && f(v))
+    }
+}
+
+impl Default for MaskedCellBuffer {
+    /// Constructs an empty `UInt8` buffer with an empty mask.
+    fn default() -> Self {
+        Self::new(CellBuffer::default(), Mask::default())
+    }
 }
 
 impl Debug for MaskedCellBuffer {
@@ -236,7 +598,87 @@ impl Debug for MaskedCellBuffer {
 
 impl From<MaskedCellBuffer> for (CellBuffer, Mask) {
     fn from(value: MaskedCellBuffer) -> Self {
-        (value.0, value.1)
+        value.into_buffer_and_mask()
+    }
+}
+
+impl CellBuffer {
+    /// Pair `self` with an all-`true` [`Mask`] of the same length.
+    ///
+    /// This mirrors [`MaskedCellBuffer::into_buffer_and_mask`] for APIs accepting
+    /// a separate `(CellBuffer, Mask)`.
+    pub fn into_buffer_and_mask(self) -> (CellBuffer, Mask) {
+        let len = self.len();
+        (self, Mask::fill(len, true))
+    }
+
+    /// Pair `self` with `mask`, constructing a [`MaskedCellBuffer`].
+    ///
+    /// Returns `Err(LengthMismatch)` if `mask` is not the same length as `self`.
+    pub fn with_mask(self, mask: Mask) -> crate::error::Result<MaskedCellBuffer> {
+        if mask.len() != self.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: mask.len() });
+        }
+        Ok(MaskedCellBuffer(self, mask))
+    }
+
+    /// Create a [`Mask`] that is `true` where the cell is greater than `value`.
+    ///
+    /// Comparisons follow [`CellValue`]'s ordering, unifying cell-types first and
+    /// ordering floating-point values via `total_cmp`, so `NaN` is greater than any
+    /// other value.
+    ///
+    /// **Note**: This shadows [`PartialOrd::gt`] in method-call syntax; use
+    /// `a > b` to compare whole buffers.
+    pub fn gt(&self, value: CellValue) -> Mask {
+        self.into_iter().map(|v| v > value).collect()
+    }
+
+    /// Create a [`Mask`] that is `true` where the cell is greater than or equal to `value`.
+    ///
+    /// See [`Self::gt`] for comparison semantics.
+    pub fn ge(&self, value: CellValue) -> Mask {
+        self.into_iter().map(|v| v >= value).collect()
+    }
+
+    /// Create a [`Mask`] that is `true` where the cell is less than `value`.
+    ///
+    /// See [`Self::gt`] for comparison semantics.
+    pub fn lt(&self, value: CellValue) -> Mask {
+        self.into_iter().map(|v| v < value).collect()
+    }
+
+    /// Create a [`Mask`] that is `true` where the cell is less than or equal to `value`.
+    ///
+    /// See [`Self::gt`] for comparison semantics.
+    pub fn le(&self, value: CellValue) -> Mask {
+        self.into_iter().map(|v| v <= value).collect()
+    }
+
+    /// Create a [`Mask`] that is `true` where the cell equals `value`.
+    ///
+    /// See [`Self::gt`] for comparison semantics.
+    pub fn eq_value(&self, value: CellValue) -> Mask {
+        self.into_iter().map(|v| v == value).collect()
+    }
+
+    /// Create a [`Mask`] that is `true` where the cell does not equal `value`.
+    ///
+    /// See [`Self::gt`] for comparison semantics.
+    pub fn ne_value(&self, value: CellValue) -> Mask {
+        self.into_iter().map(|v| v != value).collect()
+    }
+
+    /// Create a [`Mask`] that is `true` where the cell in `self` is greater than the cell
+    /// at the same position in `other`.
+    ///
+    /// Returns `Err(LengthMismatch)` if the buffers differ in length. See [`Self::gt`] for
+    /// comparison semantics.
+    pub fn gt_buffer(&self, other: &CellBuffer) -> crate::error::Result<Mask> {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { lhs: self.len(), rhs: other.len() });
+        }
+        Ok(self.into_iter().zip(other).map(|(l, r)| l > r).collect())
     }
 }
 
@@ -286,6 +728,19 @@ impl<C: CellEncoding> Extend<(C, bool)> for MaskedCellBuffer {
     }
 }
 
+/// Extends the buffer with erased values and their mask values.
+///
+/// If a value cannot be represented in the buffer's current [`CellType`], the buffer is
+/// first widened to the [union][CellType::union] of the two cell-types.
+impl Extend<(CellValue, bool)> for MaskedCellBuffer {
+    fn extend<T: IntoIterator<Item = (CellValue, bool)>>(&mut self, iter: T) {
+        for (v, m) in iter {
+            self.buffer_mut().extend(Some(v));
+            self.mask_mut().extend(Some(m));
+        }
+    }
+}
+
 impl<'buf> IntoIterator for &'buf MaskedCellBuffer {
     type Item = (CellValue, bool);
     type IntoIter = MaskedCellBufferIterator<'buf>;
@@ -317,21 +772,33 @@ impl Iterator for MaskedCellBufferIterator<'_> {
 }
 
 mod ops {
-    use crate::{CellValue, MaskedCellBuffer};
+    use crate::error::Result;
+    use crate::{with_ct, CellValue, MaskedCellBuffer};
+    use paste::paste;
     use std::ops::{Add, Div, Mul, Neg, Sub};
 
     macro_rules! cb_bin_op {
-        ($trt:ident, $mth:ident, $op:tt) => {
-            // Both borrows.
-            impl $trt for &MaskedCellBuffer {
-                type Output = MaskedCellBuffer;
-                fn $mth(self, rhs: Self) -> Self::Output {
+        ($trt:ident, $mth:ident, $op:tt) => { paste! {
+            impl MaskedCellBuffer {
+                #[doc = concat!("Element-wise `", stringify!($op), "` of `self` and `rhs`, with the resulting")]
+                /// mask being the logical AND of both masks.
+                ///
+                /// Returns `Err(LengthMismatch)` if the buffers differ in length.
+                pub fn [<try_ $mth>](&self, rhs: &MaskedCellBuffer) -> Result<MaskedCellBuffer> {
                     let (lbuf, lmask) = self.into();
                     let (rbuf, rmask) = rhs.into();
-                    let new_buf = lbuf.into_iter().zip(rbuf.into_iter()).map(|(l, r)| l $op r).collect();
+                    let new_buf = lbuf.[<try_ $mth>](rbuf)?;
                     #[allow(clippy::suspicious_arithmetic_impl)]
                     let new_mask = lmask & rmask;
-                    Self::Output::new(new_buf, new_mask)
+                    Ok(MaskedCellBuffer::new(new_buf, new_mask))
+                }
+            }
+            // Both borrows.
+            #[doc = concat!("# Panics\nWill panic if the operands are not the same length. See [`MaskedCellBuffer::try_", stringify!($mth), "`].")]
+            impl $trt for &MaskedCellBuffer {
+                type Output = MaskedCellBuffer;
+                fn $mth(self, rhs: Self) -> Self::Output {
+                    self.[<try_ $mth>](rhs).unwrap_or_else(|e| panic!("{e}"))
                 }
             }
             // Both owned/consumed
@@ -349,7 +816,6 @@ mod ops {
                 }
             }
             // RHS scalar
-            // TODO: figure out how to implement LHS scalar, avoiding orphan rule.
             impl<R> $trt<R> for MaskedCellBuffer
             where
                 R: Into<CellValue>,
@@ -362,13 +828,41 @@ mod ops {
                     Self::new(new_buf, mask)
                 }
             }
-        };
+        }};
     }
     cb_bin_op!(Add, add, +);
     cb_bin_op!(Sub, sub, -);
     cb_bin_op!(Mul, mul, *);
     cb_bin_op!(Div, div, /);
 
+    // LHS scalar. The orphan rule requires a concrete impl for each primitive.
+    macro_rules! cb_lhs_scalar_op {
+        ($trt:ident, $mth:ident, $op:tt, $($p:ty),*) => { $(
+            impl $trt<&MaskedCellBuffer> for $p {
+                type Output = MaskedCellBuffer;
+                fn $mth(self, rhs: &MaskedCellBuffer) -> Self::Output {
+                    Self::Output::new($trt::$mth(self, rhs.buffer()), rhs.mask().clone())
+                }
+            }
+            impl $trt<MaskedCellBuffer> for $p {
+                type Output = MaskedCellBuffer;
+                fn $mth(self, rhs: MaskedCellBuffer) -> Self::Output {
+                    let (buf, mask) = rhs.into();
+                    Self::Output::new($trt::$mth(self, buf), mask)
+                }
+            }
+        )* };
+    }
+    macro_rules! cb_lhs_scalar {
+        ( $(($_id:ident, $p:ty)),*) => {
+            cb_lhs_scalar_op!(Add, add, +, $($p),*);
+            cb_lhs_scalar_op!(Sub, sub, -, $($p),*);
+            cb_lhs_scalar_op!(Mul, mul, *, $($p),*);
+            cb_lhs_scalar_op!(Div, div, /, $($p),*);
+        };
+    }
+    with_ct!(cb_lhs_scalar);
+
     impl Neg for &MaskedCellBuffer {
         type Output = MaskedCellBuffer;
         fn neg(self) -> Self::Output {
@@ -385,7 +879,10 @@ mod ops {
 
 #[cfg(test)]
 mod tests {
-    use crate::{BufferOps, CellBuffer, CellType, CellValue, Mask, MaskedCellBuffer, NoData};
+    use crate::error::Error;
+    use crate::{
+        BufferOps, CellBuffer, CellType, CellValue, Mask, MaskedCellBuffer, NoData, NoDataSet,
+    };
 
     fn filler(i: usize) -> u8 {
         i as u8
@@ -407,6 +904,11 @@ mod tests {
         assert_eq!(m.mask().counts(), (4, 0));
         let m = MaskedCellBuffer::with_defaults(4, CellType::Int16);
         assert_eq!(m.mask().counts(), (4, 0));
+
+        let m = MaskedCellBuffer::default();
+        assert!(m.is_empty());
+        assert!(m.mask().is_empty());
+        assert_eq!(m.cell_type(), CellType::UInt8);
     }
 
     #[test]
@@ -424,6 +926,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn vec_with_nodata_values() {
+        let v = vec![0u8, 1, 255, 3, 0];
+        let nd = NoDataSet::new(vec![0u8, 255]);
+        let m = MaskedCellBuffer::from_vec_with_nodata_set(v.clone(), &nd);
+        assert_eq!(m.mask(), &Mask::new(vec![false, true, false, true, false]));
+        assert_eq!(m.counts(), (2, 3));
+
+        // The first sentinel is written back.
+        let r = m.to_vec_with_nodata_set(&nd).unwrap();
+        assert_eq!(r, vec![0u8, 1, 0, 3, 0]);
+    }
+
     #[test]
     fn get_masked() {
         let mut buf = MaskedCellBuffer::fill_with_mask_via(9, filler_masker);
@@ -439,6 +954,251 @@ mod tests {
         assert_eq!(buf.get_masked(5), None);
     }
 
+    #[test]
+    fn slice() {
+        let buf = MaskedCellBuffer::fill_with_mask_via(5, filler_masker);
+        assert_eq!(buf.slice(..), buf);
+        let r = buf.slice(2..);
+        assert_eq!(r.buffer(), &CellBuffer::from_vec(vec![2u8, 3, 4]));
+        assert_eq!(r.mask(), &Mask::new(vec![true, false, true]));
+        let r = buf.slice(1..3);
+        assert_eq!(r.get_masked(0), None);
+        assert_eq!(r.get_masked(1), Some(2u8.into()));
+    }
+
+    #[test]
+    fn filter_valid() {
+        let buf = MaskedCellBuffer::fill_with_mask_via(6, |i| (i as u8, i != 2));
+        let even = buf.filter_valid(|v| v.get::<u8>().unwrap() % 2 == 0);
+        assert_eq!(even, CellBuffer::from_vec(vec![0u8, 4]));
+    }
+
+    #[test]
+    fn fold_reduce() {
+        let buf = MaskedCellBuffer::fill_with_mask_via(6, |i| (1u8 << i, i != 1));
+        let xor = buf.fold(0u8, |acc, v| acc ^ v.get::<u8>().unwrap());
+        assert_eq!(xor, 0b111101);
+        assert_eq!(buf.reduce(|a, b| a.max(b)), Some(CellValue::UInt8(32)));
+        let empty = MaskedCellBuffer::fill_with_mask_via(3, |i| (i as u8, false));
+        assert_eq!(empty.reduce(|a, b| a + b), None);
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = MaskedCellBuffer::fill_with_mask_via(6, |i| (i as f64 * 0.1, i != 2));
+        let mut b = MaskedCellBuffer::fill_with_mask_via(6, |i| ((i as f64 * 0.1) as f32, i != 2));
+        assert!(a.approx_eq(&b, 1e-6));
+        // Invalid cells are ignored.
+        b.buffer_mut().put(2, 100f32.into()).unwrap();
+        assert!(a.approx_eq(&b, 1e-6));
+        b.buffer_mut().put(3, 100f32.into()).unwrap();
+        assert!(!a.approx_eq(&b, 1e-6));
+
+        let c = MaskedCellBuffer::new(a.buffer().clone(), Mask::fill(6, true));
+        assert!(!a.approx_eq(&c, 1e-6));
+    }
+
+    #[test]
+    fn compress_expand() {
+        let buf = MaskedCellBuffer::fill_with_mask_via(7, |i| (i as i32 * 3, i % 3 != 0));
+        let compressed = buf.compress();
+        assert_eq!(compressed.len(), buf.counts().0);
+        assert_eq!(compressed, CellBuffer::from_vec(vec![3, 6, 12, 15]));
+
+        let fill = CellValue::Int32(-1);
+        let expanded = MaskedCellBuffer::expand(&compressed, buf.mask(), fill).unwrap();
+        assert_eq!(expanded.mask(), buf.mask());
+        for (i, (v, m)) in expanded.into_iter().enumerate() {
+            if m {
+                assert_eq!(Some(v), buf.get_masked(i));
+            } else {
+                assert_eq!(v, fill);
+            }
+        }
+
+        let err = MaskedCellBuffer::expand(&compressed.slice(1..), buf.mask(), fill);
+        assert!(matches!(err, Err(Error::LengthMismatch { lhs: 3, rhs: 4 })));
+        assert!(MaskedCellBuffer::expand(&compressed, buf.mask(), 1.5.into()).is_err());
+    }
+
+    #[test]
+    fn with_mask() {
+        let buf = CellBuffer::fill_via(4, filler);
+        let mask = Mask::fill_via(4, masker);
+        let m = buf.clone().with_mask(mask.clone()).unwrap();
+        assert_eq!(m, MaskedCellBuffer::new(buf.clone(), mask));
+        let r = buf.with_mask(Mask::fill(3, true));
+        assert!(matches!(r, Err(Error::LengthMismatch { lhs: 4, rhs: 3 })));
+    }
+
+    #[test]
+    fn comparisons() {
+        let buf = CellBuffer::from_vec(vec![1u8, 5, 7, 10]);
+        assert_eq!(buf.gt(5.into()), Mask::new(vec![false, false, true, true]));
+        assert_eq!(buf.ge(5.into()), Mask::new(vec![false, true, true, true]));
+        assert_eq!(
+            buf.lt(5.5.into()),
+            Mask::new(vec![true, true, false, false])
+        );
+        assert_eq!(buf.le((-1i8).into()), Mask::fill(4, false));
+        assert_eq!(
+            buf.eq_value(7.0.into()),
+            Mask::new(vec![false, false, true, false])
+        );
+        assert_eq!(
+            buf.ne_value(7.into()),
+            Mask::new(vec![true, true, false, true])
+        );
+        assert!(CellBuffer::default().gt(0.into()).is_empty());
+
+        let other = CellBuffer::from_vec(vec![2.0, 4.0, 7.0, f64::NAN]);
+        assert_eq!(
+            buf.gt_buffer(&other).unwrap(),
+            Mask::new(vec![false, true, false, false])
+        );
+        let r = buf.gt_buffer(&other.slice(..2));
+        assert!(matches!(r, Err(Error::LengthMismatch { lhs: 4, rhs: 2 })));
+    }
+
+    #[test]
+    fn and_where() {
+        let mut m = MaskedCellBuffer::from_vec_with_nodata(vec![0u8, 3, 8, 5, 9], NoData::new(9));
+        m.and_where(|v| v >= 4.into());
+        assert_eq!(m.mask(), &Mask::new(vec![false, false, true, true, false]));
+        // Predicate isn't evaluated on invalid cells.
+        m.and_where(|v| {
+            assert!(v != 9.into());
+            true
+        });
+        assert_eq!(m.counts(), (2, 3));
+    }
+
+    #[test]
+    fn div_masking_nonfinite() {
+        let nir = MaskedCellBuffer::from_vec_with_nodata(vec![4u8, 0, 6, 8], NoData::new(8));
+        let red = MaskedCellBuffer::from(CellBuffer::from_vec(vec![2u8, 0, 0, 2]));
+        let r = nir.div_masking_nonfinite(&red).unwrap();
+        assert_eq!(r.mask(), &Mask::new(vec![true, false, false, false]));
+        assert_eq!(r.get_masked(0), Some(2.0.into()));
+        // Plain division keeps non-finite results valid.
+        assert_eq!((&nir / &red).counts(), (3, 1));
+        let r = nir.div_masking_nonfinite(&red.slice(..2));
+        assert!(matches!(r, Err(Error::LengthMismatch { .. })));
+    }
+
+    #[test]
+    fn dot() {
+        let a = MaskedCellBuffer::from_vec_with_nodata(vec![1i16, -2, 3, 300], NoData::new(300));
+        let b = MaskedCellBuffer::from_vec_with_nodata(vec![4i16, 5, -6, 200], NoData::new(5));
+        assert!(matches!(a.dot(&b).unwrap(), CellValue::Int16(-14)));
+        let all = MaskedCellBuffer::from(a.buffer().clone());
+        assert_eq!(all.dot(&all).unwrap(), a.buffer().dot(a.buffer()).unwrap());
+        assert!(matches!(
+            a.dot(&b.slice(..1)),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn argmin_argmax() {
+        let buf = MaskedCellBuffer::from_vec_with_nodata(vec![9u8, 3, 0, 7, 12, 1], NoData::new(9));
+        assert_eq!(buf.argmin(), Some(2));
+        assert_eq!(buf.argmax(), Some(4));
+        let mut masked = buf.clone();
+        masked.and_where(|v| v != 0.into() && v != 12.into());
+        assert_eq!((masked.argmin(), masked.argmax()), (Some(5), Some(3)));
+        masked.set_mask(Mask::fill(6, false)).unwrap();
+        assert_eq!((masked.argmin(), masked.argmax()), (None, None));
+    }
+
+    #[test]
+    fn swap() {
+        let orig = MaskedCellBuffer::fill_with_mask_via(4, filler_masker);
+        let mut buf = orig.clone();
+        buf.swap(0, 3);
+        assert_eq!(buf.get_with_mask(0), (CellValue::UInt8(3), false));
+        assert_eq!(buf.get_with_mask(3), (CellValue::UInt8(0), true));
+        buf.swap(3, 0);
+        assert_eq!(buf, orig);
+    }
+
+    #[test]
+    fn summary() {
+        let buf = MaskedCellBuffer::from_vec_with_nodata(
+            vec![2u8, 4, 0, 4, 4, 5, 5, 7, 9, 0],
+            NoData::new(0),
+        );
+        let s = buf.summary();
+        assert_eq!((s.count, s.valid_count), (10, 8));
+        assert_eq!((s.min, s.max), (CellValue::UInt8(2), CellValue::UInt8(9)));
+        assert_eq!((s.mean, s.std_dev, s.median), (5.0, 2.0, 4.5));
+        assert_eq!(s, {
+            let mut e = buf.compress().summary();
+            e.count = 10;
+            e
+        });
+    }
+
+    #[test]
+    fn set_mask() {
+        let mut m = MaskedCellBuffer::from(CellBuffer::fill_via(4, filler));
+        m.set_mask(Mask::fill_via(4, masker)).unwrap();
+        assert_eq!(m.counts(), (2, 2));
+        let r = m.set_mask(Mask::fill(5, true));
+        assert!(matches!(r, Err(Error::LengthMismatch { lhs: 4, rhs: 5 })));
+        assert_eq!(m.mask(), &Mask::fill_via(4, masker));
+    }
+
+    #[test]
+    fn split_at() {
+        let buf = MaskedCellBuffer::fill_with_mask_via(5, filler_masker);
+        for mid in 0..=5 {
+            let (l, r) = buf.split_at(mid);
+            assert_eq!(l.len(), mid);
+            assert_eq!(l.mask().len(), mid);
+            let mut joined = l.clone();
+            joined.extend(&r);
+            assert_eq!(joined, buf);
+        }
+    }
+
+    #[test]
+    fn chunks() {
+        let buf = MaskedCellBuffer::fill_with_mask_via(5, filler_masker);
+        let chunks: Vec<_> = buf.chunks(2).collect();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks[1],
+            MaskedCellBuffer::fill_with_mask_via(2, |i| filler_masker(i + 2))
+        );
+        assert_eq!(chunks[2].counts(), (1, 0));
+    }
+
+    #[test]
+    fn validate() {
+        let mut buf = MaskedCellBuffer::fill_with_mask_via(4, filler_masker);
+        assert!(buf.validate().is_ok());
+        buf.mask_mut().push(true);
+        assert!(matches!(
+            buf.validate(),
+            Err(Error::LengthMismatch { lhs: 4, rhs: 5 })
+        ));
+    }
+
+    #[test]
+    fn into_buffer_and_mask() {
+        let buf = CellBuffer::fill_via(5, filler);
+        let (b, m) = buf.clone().into_buffer_and_mask();
+        assert_eq!(b, buf);
+        assert_eq!(m.len(), 5);
+        assert!(m.all(true));
+
+        let mbuf = MaskedCellBuffer::fill_with_mask_via(5, filler_masker);
+        let (b, m) = mbuf.into_buffer_and_mask();
+        assert_eq!(b, buf);
+        assert_eq!(m, Mask::fill_via(5, masker));
+    }
+
     #[test]
     fn convert() {
         let buf = MaskedCellBuffer::fill_with_mask_via(4, filler_masker);
@@ -454,6 +1214,35 @@ mod tests {
         assert_eq!(buf.get_masked(3), None);
     }
 
+    #[test]
+    fn push_resize() {
+        let mut buf = MaskedCellBuffer::fill_with_mask_via(2, filler_masker);
+        buf.push(7u8.into(), false).unwrap();
+        buf.push(8u8.into(), true).unwrap();
+        assert!(buf.push((-1).into(), true).is_err());
+        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.get_masked(2), None);
+        assert_eq!(buf.get_masked(3), Some(8u8.into()));
+
+        // Grow
+        buf.resize(6, 9u8.into()).unwrap();
+        assert_eq!(buf.get_masked(5), Some(9u8.into()));
+        assert_eq!(buf.counts(), (4, 2));
+        // Shrink
+        buf.resize(1, 0u8.into()).unwrap();
+        assert_eq!(buf, MaskedCellBuffer::fill_with_mask_via(1, filler_masker));
+    }
+
+    #[test]
+    fn extend_values() {
+        let mut buf = MaskedCellBuffer::fill(2, 0u8.into());
+        buf.extend([(CellValue::UInt8(1), false), (CellValue::Int16(-1), true)]);
+        assert_eq!(buf.cell_type(), CellType::Int16);
+        assert_eq!(buf.get_masked(2), None);
+        assert_eq!(buf.get_masked(3), Some((-1i16).into()));
+        assert_eq!(buf.counts(), (3, 1));
+    }
+
     #[test]
     fn from_iter() {
         let buf: MaskedCellBuffer = (0..5i16).collect();
@@ -530,6 +1319,96 @@ mod tests {
         test_ops!(+ - * /);
     }
 
+    #[test]
+    fn try_binary() {
+        let lhs = MaskedCellBuffer::fill_with_mask_via(4, filler_masker);
+        let rhs = MaskedCellBuffer::fill(4, 2u8.into());
+        assert_eq!(lhs.try_mul(&rhs).unwrap(), &lhs * &rhs);
+        let err = lhs.try_add(&rhs.slice(..3)).unwrap_err();
+        assert!(matches!(err, Error::LengthMismatch { lhs: 4, rhs: 3 }));
+        assert_eq!(err.to_string(), "Length mismatch between 4 and 3");
+    }
+
+    #[test]
+    fn entropy() {
+        // Valid cells are all `0`, with the odd positions masked out.
+        let buf = MaskedCellBuffer::fill_with_mask_via(8, |i| ((i % 2) as u8, masker(i)));
+        assert_eq!(buf.value_counts().len(), 1);
+        assert_eq!(buf.shannon_entropy(), 0.0);
+
+        let buf = MaskedCellBuffer::fill_with_mask_via(8, |i| ((i % 4) as u8, masker(i)));
+        assert_eq!(buf.shannon_entropy(), 1.0);
+    }
+
+    #[test]
+    fn lhs_scalar() {
+        let mbuf = MaskedCellBuffer::fill_with_mask_via(9, |i| (filler(i), masker(i)));
+        let r = 2.0 * &mbuf;
+        assert_eq!(r, mbuf.clone() * 2.0);
+        let r: MaskedCellBuffer = 10 - mbuf.clone();
+        assert_eq!(r.mask(), mbuf.mask());
+        assert_eq!(r.get_masked(2), Some(CellValue::Float64(8.0)));
+        assert_eq!(r.get_masked(1), None);
+    }
+
+    #[test]
+    fn zip_with() {
+        let a = MaskedCellBuffer::new(
+            CellBuffer::from_vec(vec![1u8, 200, 3]),
+            Mask::new(vec![true, false, true]),
+        );
+        let b = MaskedCellBuffer::new(
+            CellBuffer::from_vec(vec![-4i16, 5, 300]),
+            Mask::new(vec![true, true, false]),
+        );
+        let r = a.zip_with(&b, |l, r| l - r).unwrap();
+        assert_eq!(r.mask(), &Mask::new(vec![true, false, false]));
+        assert_eq!(r.get_masked(0), Some(CellValue::Float64(5.0)));
+        assert!(a.zip_with(&b.slice(1..), |l, _| l).is_err());
+    }
+
+    #[test]
+    fn predicates() {
+        let buf = MaskedCellBuffer::new(
+            CellBuffer::from_vec(vec![9u8, 2, 7, 5, 8]),
+            Mask::new(vec![false, true, true, true, true]),
+        );
+        let above = |v: CellValue| v > 6.into();
+        assert_eq!(buf.count_where(above), 2);
+        assert_eq!(buf.position(above), Some(2));
+        assert_eq!(buf.find(above), Some(7u8.into()));
+        assert_eq!(buf.find(|v| v > 8.into()), None);
+    }
+
+    #[test]
+    fn count_in_range() {
+        let buf = MaskedCellBuffer::new(
+            CellBuffer::from_vec(vec![0u8, 5, 10, 15, 7]),
+            Mask::new(vec![true, true, false, true, true]),
+        );
+        assert_eq!(buf.count_in_range(4.into(), 11.into()), 2);
+        assert_eq!(buf.count_out_of_range(4.into(), 11.into()), 2);
+    }
+
+    #[test]
+    fn pearson() {
+        // Invalid cells would otherwise break the linear relationship.
+        let x = MaskedCellBuffer::fill_with_mask_via(10, |i| (i as f64, i != 3));
+        let y = MaskedCellBuffer::fill_with_mask_via(10, |i| match i {
+            7 => (100.0, false),
+            _ => (i as f64 * -2.0 + 1000.0, true),
+        });
+        assert!((x.pearson(&y).unwrap() + 1.0).abs() < 1e-12);
+        assert!(x.pearson(&x.slice(..5)).is_err());
+    }
+
+    #[test]
+    fn to_string_vec() {
+        let buf = MaskedCellBuffer::fill_with_mask_via(4, |i| (i as f64 * 1.5, masker(i)));
+        assert_eq!(buf.to_string_vec(), ["0", "", "3", ""]);
+        assert_eq!(buf.to_string_vec_with("NA"), ["0", "NA", "3", "NA"]);
+    }
+
     #[test]
     fn debug() {
         let m: MaskedCellBuffer = (0..1).collect();
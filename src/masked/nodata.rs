@@ -1,18 +1,33 @@
-use crate::{CellEncoding, CellType, CellValue};
+use crate::{CellEncoding, CellValue};
+use num_traits::ToPrimitive;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Encodes a no-data value for cells that should be considered invalid
 /// or masked-out of a result.
+///
+/// Floating-point `NaN` no-data values match any `NaN` cell. See [`NoData::is`].
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NoData<T: CellEncoding> {
     /// Case where there is no no-data value.
     None,
     /// Case where there the default no-data value should be used.
+    ///
+    /// This is the cell-type's minimum value for integral types, and `NaN` for
+    /// floating-point types.
+    ///
+    /// **Warning**: For unsigned types the minimum value is `0`, which is
+    /// frequently a legitimate value in real data. Consider [`NoData::DefaultMax`]
+    /// or an explicit [`NoData::Value`] instead.
     #[default]
     Default,
+    /// Case where the cell-type's maximum value should be used for integral types,
+    /// and `NaN` for floating-point types.
+    DefaultMax,
     /// Case where a specific no-data value is specified.
+    ///
+    /// See [`NoDataSet`] for data with several no-data values.
     Value(T),
 }
 
@@ -20,35 +35,77 @@ impl<T: CellEncoding> NoData<T> {
     pub fn new(value: T) -> Self {
         NoData::Value(value)
     }
+    /// Get the no-data value, if any.
     pub fn value(&self) -> Option<T> {
         match self {
             NoData::None => None,
             NoData::Value(v) => Some(*v),
-            NoData::Default => match T::cell_type() {
-                CellType::UInt8 => T::static_cast(<u8>::MIN),
-                CellType::UInt16 => T::static_cast(<u16>::MIN),
-                CellType::UInt32 => T::static_cast(<u32>::MIN),
-                CellType::UInt64 => T::static_cast(<u64>::MIN),
-                CellType::Int8 => T::static_cast(<i8>::MIN),
-                CellType::Int16 => T::static_cast(<i16>::MIN),
-                CellType::Int32 => T::static_cast(<i32>::MIN),
-                CellType::Int64 => T::static_cast(<i64>::MIN),
-                CellType::Float32 => T::static_cast(<f32>::NAN),
-                CellType::Float64 => T::static_cast(<f64>::NAN),
-            },
+            NoData::Default => Some(T::nodata_default()),
+            NoData::DefaultMax if T::cell_type().is_integral() => Some(T::MAX),
+            NoData::DefaultMax => Some(T::nodata_default()),
         }
     }
     /// Determines if `value` should be considered a "no-data" value.
+    ///
+    /// Values are compared via [`CellValue`] equality, except for `NaN`s: a `NaN` no-data
+    /// value (including that of [`NoData::Default`] for floating-point types) matches any
+    /// `NaN`, regardless of sign or payload bits, while a non-`NaN` no-data value (e.g.
+    /// `NoData::new(-9999.0)`) never matches a `NaN`.
     pub fn is(&self, value: &CellValue) -> bool {
-        if let Some(nd_val) = self.value() {
-            let nd_val2 = nd_val.into_cell_value();
-            &nd_val2 == value
-        } else {
-            false
-        }
+        self.value().is_some_and(|nd_val| matches(nd_val, value))
+    }
+}
+
+/// A set of no-data values, for data where any of several sentinels (e.g. both `0` and
+/// `255`) mark cells invalid.
+///
+/// The first value is considered the canonical one, and is used when writing no-data
+/// values (e.g. [`MaskedCellBuffer::to_vec_with_nodata_set`][crate::MaskedCellBuffer::to_vec_with_nodata_set]).
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NoDataSet<T: CellEncoding>(Vec<T>);
+
+impl<T: CellEncoding> NoDataSet<T> {
+    /// Create a new set of no-data `values`.
+    pub fn new(values: Vec<T>) -> Self {
+        Self(values)
+    }
+    /// Get the listed no-data values.
+    pub fn values(&self) -> &[T] {
+        &self.0
+    }
+    /// Get the canonical (first) no-data value, if any.
+    pub fn value(&self) -> Option<T> {
+        self.0.first().copied()
+    }
+    /// Determines if `value` matches any of the no-data values.
+    ///
+    /// Values are compared as in [`NoData::is`].
+    pub fn is(&self, value: &CellValue) -> bool {
+        self.0.iter().any(|nd_val| matches(*nd_val, value))
+    }
+}
+
+impl<T: CellEncoding> From<Vec<T>> for NoDataSet<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self(values)
+    }
+}
+
+/// Compare a no-data value against `value`, treating all `NaN`s as equal.
+fn matches<T: CellEncoding>(nd_val: T, value: &CellValue) -> bool {
+    let nd_val = nd_val.into_cell_value();
+    if is_nan(&nd_val) || is_nan(value) {
+        is_nan(&nd_val) && is_nan(value)
+    } else {
+        &nd_val == value
     }
 }
 
+fn is_nan(value: &CellValue) -> bool {
+    !value.cell_type().is_integral() && value.to_f64().is_some_and(f64::is_nan)
+}
+
 /// Trait for no-data testing.
 pub trait IsNodata {
     /// Determines if the `self` matches given `NoData` value.
@@ -69,21 +126,29 @@ impl<T: CellEncoding> IsNodata for T {
 
 #[cfg(test)]
 mod tests {
-    use crate::{with_ct, IsNodata, NoData};
+    use crate::{with_ct, IsNodata, NoData, NoDataSet};
 
     #[test]
     fn has_value() {
         assert_eq!(NoData::<i16>::None.value(), None);
         assert_eq!(NoData::<u8>::Default.value(), Some(<u8>::MIN));
         assert!(NoData::<f32>::Default.value().unwrap().is_nan());
+        assert_eq!(NoData::<u8>::DefaultMax.value(), Some(255));
+        assert_eq!(NoData::<i16>::DefaultMax.value(), Some(i16::MAX));
+        assert!(NoData::<f64>::DefaultMax.value().unwrap().is_nan());
         assert_eq!(NoData::new(6u16).value(), Some(6u16));
+        assert_eq!(NoDataSet::new(vec![0u8, 255]).value(), Some(0u8));
+        assert_eq!(NoDataSet::<u8>::new(vec![]).value(), None);
     }
 
     #[test]
     fn defaults() {
         macro_rules! test {
-            ($( ($id:ident, $p:ident) ),*) => {
-                $(assert!(NoData::<$p>::Default.value().is_some());)*
+            ($( ($id:ident, $p:ty) ),*) => {
+                $(
+                assert!(NoData::<$p>::Default.value().is_some());
+                assert!(NoData::<$p>::DefaultMax.value().is_some());
+                )*
             }
         }
         with_ct!(test);
@@ -92,5 +157,38 @@ mod tests {
     #[test]
     fn is_nodata() {
         assert!(f64::NAN.is(NoData::<f64>::Default));
+        let nd = NoData::new(255u8);
+        assert!(255u8.is(nd));
+        assert!(!3u8.is(nd));
+    }
+
+    #[test]
+    fn is_nodata_set() {
+        let nd = NoDataSet::new(vec![0u8, 255]);
+        assert!(nd.is(&0u8.into()));
+        assert!(nd.is(&255u8.into()));
+        assert!(!nd.is(&3u8.into()));
+        assert!(!NoDataSet::<u8>::default().is(&0u8.into()));
+    }
+
+    #[test]
+    fn is_nan_nodata() {
+        let quiet = f64::from_bits(0x7ff8_0000_0000_0001);
+        let signaling = f64::from_bits(0x7ff0_0000_0000_0001);
+        let negative = -f64::NAN;
+        for nan in [f64::NAN, quiet, signaling, negative] {
+            assert!(nan.is_nan());
+            assert!(nan.is(NoData::<f64>::Default), "{:x}", nan.to_bits());
+            assert!(nan.is(NoData::<f64>::DefaultMax), "{:x}", nan.to_bits());
+            assert!(nan.is(NoData::new(f64::NAN)), "{:x}", nan.to_bits());
+            assert!(nan.is(NoData::new(signaling)), "{:x}", nan.to_bits());
+            assert!(NoDataSet::new(vec![-9999.0, quiet]).is(&nan.into()));
+            assert!(!nan.is(NoData::new(-9999.0)), "{:x}", nan.to_bits());
+            assert!(!(nan as f32).is(NoData::new(-9999.0f32)));
+            assert!((nan as f32).is(NoData::<f32>::Default));
+        }
+        assert!(!1.0.is(NoData::<f64>::Default));
+        assert!((-9999.0).is(NoData::new(-9999.0)));
+        assert!(!0u8.is(NoData::new(f32::NAN)));
     }
 }
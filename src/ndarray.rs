@@ -0,0 +1,105 @@
+//! `erased-cells` connector to the `ndarray` crate.
+
+use ndarray::{Array, Array1, Array2, Dimension};
+
+use crate::error::{Error, Result};
+use crate::{BufferOps, CellBuffer, CellEncoding};
+
+/// Convert `array` into a `Vec` in logical (row-major) order, reusing its
+/// allocation when in standard layout.
+fn into_logical_vec<T: Clone, D: Dimension>(array: Array<T, D>) -> Vec<T> {
+    if !array.is_standard_layout() {
+        return array.iter().cloned().collect();
+    }
+    let len = array.len();
+    let (mut v, offset) = array.into_raw_vec_and_offset();
+    let offset = offset.unwrap_or(0);
+    v.truncate(offset + len);
+    v.drain(..offset);
+    v
+}
+
+impl CellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    /// Construct a [`CellBuffer`] from a 1-D array, without copying when in standard layout.
+    pub fn from_array1<T: CellEncoding>(array: Array1<T>) -> Self {
+        Self::from_vec(into_logical_vec(array))
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    /// Convert `self` into a 1-D array, without copying when `T` matches `self.cell_type()`.
+    ///
+    /// Returns `Err(NarrowingError)` if the cells cannot be converted to `T`.
+    pub fn to_array1<T: CellEncoding>(self) -> Result<Array1<T>> {
+        Ok(Array1::from_vec(self.to_vec()?))
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    /// Construct a [`CellBuffer`] from a 2-D array, returning it along with the
+    /// array's `(rows, cols)` shape.
+    ///
+    /// Cells are stored in row-major order, without copying when in standard layout.
+    pub fn from_array2<T: CellEncoding>(array: Array2<T>) -> (Self, (usize, usize)) {
+        let shape = array.dim();
+        (Self::from_vec(into_logical_vec(array)), shape)
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    /// Convert `self` into a 2-D array of `(rows, cols)` shape, interpreting cells in
+    /// row-major order. No copy is made when `T` matches `self.cell_type()`.
+    ///
+    /// Returns `Err(LengthMismatch)` if `rows * cols != self.len()` (including on overflow), or
+    /// `Err(NarrowingError)` if the cells cannot be converted to `T`.
+    pub fn to_array2<T: CellEncoding>(self, shape: (usize, usize)) -> Result<Array2<T>> {
+        let (rows, cols) = shape;
+        if rows.checked_mul(cols) != Some(self.len()) {
+            return Err(Error::LengthMismatch {
+                lhs: self.len(),
+                rhs: rows.saturating_mul(cols),
+            });
+        }
+        // `unwrap` should be ok as the length has been checked.
+        Ok(Array2::from_shape_vec(shape, self.to_vec()?).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufferOps, CellBuffer, CellType};
+    use ndarray::{array, s, Array1};
+
+    #[test]
+    fn array1() {
+        let a = Array1::from_vec(vec![1.0f32, 2.0, 3.0]);
+        let buf = CellBuffer::from_array1(a.clone());
+        assert_eq!(buf.cell_type(), CellType::Float32);
+        assert_eq!(buf.clone().to_array1::<f32>().unwrap(), a);
+        assert_eq!(buf.to_array1::<f64>().unwrap(), array![1.0, 2.0, 3.0]);
+
+        let strided = CellBuffer::from_array1(a.slice_move(s![..;2]));
+        assert_eq!(strided, CellBuffer::from_vec(vec![1.0f32, 3.0]));
+    }
+
+    #[test]
+    fn array2() {
+        let a = array![[1i16, 2, 3], [4, 5, 6], [7, 8, 9]];
+        let (buf, shape) = CellBuffer::from_array2(a.clone());
+        assert_eq!(shape, (3, 3));
+        assert_eq!(buf.cell_type(), CellType::Int16);
+        assert_eq!(buf.get(3), 4i16.into());
+        assert_eq!(buf.clone().to_array2::<i16>(shape).unwrap(), a);
+        assert!(buf.clone().to_array2::<i16>((2, 3)).is_err());
+        assert!(buf.clone().to_array2::<i16>((usize::MAX, 3)).is_err());
+        assert!(buf.to_array2::<u8>(shape).is_err());
+
+        // Non-standard layout
+        let (buf, shape) = CellBuffer::from_array2(a.clone().reversed_axes());
+        assert_eq!(shape, (3, 3));
+        assert_eq!(buf.get(1), 4i16.into());
+
+        // Offset into the original allocation
+        let (buf, shape) = CellBuffer::from_array2(a.slice_move(s![1.., ..]));
+        assert_eq!(shape, (2, 3));
+        assert_eq!(buf, CellBuffer::fill_via(6, |i| i as i16 + 4));
+    }
+}
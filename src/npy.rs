@@ -0,0 +1,249 @@
+//! `erased-cells` support for the NumPy `.npy` file format.
+//!
+//! Only 1-D arrays are supported. See the
+//! [format specification](https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html).
+
+use std::io::{Read, Write};
+
+use crate::error::{Error, Result};
+use crate::{BufferOps, CellBuffer, CellType};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+/// Total header length, including magic and version, is padded to a multiple of this.
+const ALIGNMENT: usize = 64;
+
+/// NumPy type string for `ct`, without the byte-order prefix (e.g. `u1`, `f8`).
+fn kind(ct: CellType) -> String {
+    let k = if !ct.is_integral() {
+        'f'
+    } else if ct.is_signed() {
+        'i'
+    } else {
+        'u'
+    };
+    format!("{k}{}", ct.size_of())
+}
+
+/// Extract the text following `'key':` in the header dictionary.
+fn field<'h>(header: &'h str, key: &str) -> Result<&'h str> {
+    let pat = format!("'{key}':");
+    header
+        .find(&pat)
+        .map(|i| header[i + pat.len()..].trim_start())
+        .ok_or_else(|| Error::ParseError(header.to_string(), "npy header"))
+}
+
+/// Read exactly `len` bytes from `r`.
+///
+/// Unlike `read_exact` into a pre-sized buffer, `len` (taken from an untrusted header) is not
+/// allocated up front, so a bogus length fails at end-of-input rather than aborting.
+fn read_len<R: Read>(r: &mut R, len: usize) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    r.take(len as u64).read_to_end(&mut buf)?;
+    if buf.len() != len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(buf)
+}
+
+impl CellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "npy")))]
+    /// Write `self` as a 1-D array in NumPy `.npy` (version 1.0) format.
+    pub fn write_npy<W: Write>(&self, mut w: W) -> Result<()> {
+        let ct = self.cell_type();
+        let order = if ct.size_of() == 1 { '|' } else { '<' };
+        let mut header = format!(
+            "{{'descr': '{order}{}', 'fortran_order': False, 'shape': ({},), }}",
+            kind(ct),
+            self.len()
+        );
+        // magic + version + header length + header + newline
+        let unpadded = MAGIC.len() + 2 + 2 + header.len() + 1;
+        let padding = unpadded.next_multiple_of(ALIGNMENT) - unpadded;
+        header.extend(std::iter::repeat_n(' ', padding));
+        header.push('\n');
+
+        w.write_all(MAGIC)?;
+        w.write_all(&[1, 0])?;
+        w.write_all(&(header.len() as u16).to_le_bytes())?;
+        w.write_all(header.as_bytes())?;
+        w.write_all(&self.to_le_bytes())?;
+        Ok(())
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "npy")))]
+    /// Read a 1-D array in NumPy `.npy` format.
+    ///
+    /// Returns `Err(UnsupportedCellTypeError)` if the array's `dtype` has no corresponding
+    /// [`CellType`], or `Err(ParseError)` if the format version is unknown, the header is
+    /// malformed or the array is not 1-D.
+    pub fn read_npy<R: Read>(mut r: R) -> Result<CellBuffer> {
+        let mut preamble = [0u8; 8];
+        r.read_exact(&mut preamble)?;
+        if &preamble[..MAGIC.len()] != MAGIC {
+            return Err(Error::ParseError(
+                String::from_utf8_lossy(&preamble).into_owned(),
+                "npy magic string",
+            ));
+        }
+        let header_len = match preamble[MAGIC.len()] {
+            1 => {
+                let mut len = [0u8; 2];
+                r.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0u8; 4];
+                r.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            v => {
+                return Err(Error::ParseError(
+                    format!("{v}.{}", preamble[MAGIC.len() + 1]),
+                    "npy format version",
+                ))
+            }
+        };
+        let header = read_len(&mut r, header_len)?;
+        let header = String::from_utf8_lossy(&header);
+        let parse_err = || Error::ParseError(header.to_string(), "npy header");
+
+        let descr = field(&header, "descr")?
+            .strip_prefix('\'')
+            .and_then(|d| d.split('\'').next())
+            .ok_or_else(parse_err)?;
+        let (order, k) = descr.split_at_checked(1).ok_or_else(parse_err)?;
+        let ct = CellType::iter()
+            .find(|ct| kind(*ct) == k)
+            .ok_or_else(|| Error::UnsupportedCellTypeError(descr.to_string()))?;
+        let big_endian = match order {
+            "<" | "|" => false,
+            ">" => true,
+            "=" => cfg!(target_endian = "big"),
+            _ => return Err(Error::UnsupportedCellTypeError(descr.to_string())),
+        };
+
+        let shape = field(&header, "shape")?
+            .strip_prefix('(')
+            .and_then(|s| s.split(')').next())
+            .ok_or_else(parse_err)?;
+        let dims = shape
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(|d| d.parse::<usize>().map_err(|_| parse_err()))
+            .collect::<Result<Vec<_>>>()?;
+        let [len] = dims[..] else {
+            return Err(Error::ParseError(shape.to_string(), "1-D npy shape"));
+        };
+
+        let size = len
+            .checked_mul(ct.size_of())
+            .ok_or_else(|| Error::ParseError(shape.to_string(), "npy array size"))?;
+        let data = read_len(&mut r, size)?;
+        if big_endian {
+            CellBuffer::from_be_bytes(ct, &data)
+        } else {
+            CellBuffer::from_le_bytes(ct, &data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::{BufferOps, CellBuffer, CellType};
+
+    #[test]
+    fn round_trip() {
+        for ct in CellType::iter() {
+            let mut buf = CellBuffer::with_defaults(5, ct);
+            buf.put(1, ct.one()).unwrap();
+            buf.put(2, ct.min_value()).unwrap();
+            buf.put(3, ct.max_value()).unwrap();
+
+            let mut bytes = Vec::new();
+            buf.write_npy(&mut bytes).unwrap();
+            let header_len = bytes.len() - 5 * ct.size_of();
+            assert!(header_len % 64 == 0, "{ct}");
+            assert_eq!(CellBuffer::read_npy(bytes.as_slice()).unwrap(), buf, "{ct}");
+        }
+    }
+
+    #[test]
+    fn header() {
+        let mut bytes = Vec::new();
+        CellBuffer::from_vec(vec![1.0f32, 2.0])
+            .write_npy(&mut bytes)
+            .unwrap();
+        assert_eq!(bytes.len(), 128 + 8);
+        let header = String::from_utf8_lossy(&bytes[10..128]);
+        assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (2,), }"));
+        assert!(header.ends_with('\n'));
+    }
+
+    /// Construct a version 1.0 `.npy` file with the given header dictionary and data.
+    fn npy(dict: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend((dict.len() as u16).to_le_bytes());
+        bytes.extend(dict.as_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    #[test]
+    fn big_endian() {
+        let bytes = npy(
+            "{'descr': '>u2', 'fortran_order': False, 'shape': (2,), }\n",
+            &[0x01, 0x02, 0x03, 0x04],
+        );
+        assert_eq!(
+            CellBuffer::read_npy(bytes.as_slice()).unwrap(),
+            CellBuffer::from_vec(vec![0x0102u16, 0x0304])
+        );
+    }
+
+    #[test]
+    fn rejects() {
+        let complex = npy(
+            "{'descr': '<c8', 'fortran_order': False, 'shape': (1,), }\n",
+            &[0; 8],
+        );
+        assert!(CellBuffer::read_npy(complex.as_slice()).is_err());
+
+        let matrix = npy(
+            "{'descr': '<u2', 'fortran_order': False, 'shape': (1, 2), }\n",
+            &[0; 4],
+        );
+        assert!(CellBuffer::read_npy(matrix.as_slice()).is_err());
+
+        let short = npy(
+            "{'descr': '<u2', 'fortran_order': False, 'shape': (3,), }\n",
+            &[0; 4],
+        );
+        assert!(CellBuffer::read_npy(short.as_slice()).is_err());
+
+        let huge = npy(
+            "{'descr': '<u2', 'fortran_order': False, 'shape': (18446744073709551615,), }\n",
+            &[0; 4],
+        );
+        let err = CellBuffer::read_npy(huge.as_slice()).unwrap_err();
+        assert!(matches!(err, Error::ParseError(_, "npy array size")));
+
+        let unbounded = npy(
+            "{'descr': '<u1', 'fortran_order': False, 'shape': (4294967296,), }\n",
+            &[0; 4],
+        );
+        assert!(CellBuffer::read_npy(unbounded.as_slice()).is_err());
+
+        let mut future = npy(
+            "{'descr': '<u1', 'fortran_order': False, 'shape': (1,), }\n",
+            &[0],
+        );
+        future[6] = 4;
+        let err = CellBuffer::read_npy(future.as_slice()).unwrap_err();
+        assert!(matches!(err, Error::ParseError(_, "npy format version")));
+
+        assert!(CellBuffer::read_npy(&b"not numpy"[..]).is_err());
+    }
+}
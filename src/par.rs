@@ -0,0 +1,157 @@
+//! Parallel operations over buffers, via `rayon`.
+
+use num_traits::ToPrimitive;
+use rayon::prelude::*;
+
+use crate::buffer::TotalOrd;
+use crate::{with_ct, BufferOps, CellBuffer, CellEncoding, CellValue};
+
+impl CellBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    /// Apply `f` to each cell in parallel, collecting the results into a new buffer.
+    ///
+    /// As with `FromIterator<CellValue>`, values returned by `f` with mixed cell-types are
+    /// promoted to their [`CellType::union`][crate::CellType::union].
+    pub fn par_map_values<F>(&self, f: F) -> CellBuffer
+    where
+        F: Fn(CellValue) -> CellValue + Sync,
+    {
+        macro_rules! par_map_values {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.par_iter().map(|x| f((*x).into())).collect(),)*
+                }
+            };
+        }
+        with_ct!(par_map_values)
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    /// Compute the minimum and maximum values of the buffer in parallel.
+    ///
+    /// Results are identical to [`BufferOps::min_max`][crate::BufferOps::min_max].
+    pub fn par_min_max(&self) -> (CellValue, CellValue) {
+        macro_rules! par_min_max {
+            ( $(($id:ident, $p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => {
+                        let init = || (<$p>::MAX, <$p>::MIN);
+                        let (min, max) = v
+                            .par_iter()
+                            .fold(init, |(lo, hi), x| (lo.total_min(*x), hi.total_max(*x)))
+                            .reduce(init, |(llo, lhi), (rlo, rhi)| {
+                                (llo.total_min(rlo), lhi.total_max(rhi))
+                            });
+                        (min.into(), max.into())
+                    })*
+                }
+            };
+        }
+        with_ct!(par_min_max)
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    /// Compute the sum of the buffer values in parallel, as a `Float64` [`CellValue`].
+    ///
+    /// Because floating-point addition is not associative, and the order in which
+    /// partial sums are combined is determined by `rayon`'s work splitting, the result
+    /// may differ in the least significant digits from a sequential sum.
+    pub fn par_sum(&self) -> CellValue {
+        macro_rules! par_sum {
+            ( $(($id:ident, $_p:ty)),*) => {
+                match self {
+                    $(CellBuffer::$id(v) => v.par_iter().map(|x| x.to_f64().unwrap()).sum::<f64>(),)*
+                }
+            };
+        }
+        CellValue::Float64(with_ct!(par_sum))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+impl<C: CellEncoding + Send> FromParallelIterator<C> for CellBuffer {
+    fn from_par_iter<I: IntoParallelIterator<Item = C>>(par_iter: I) -> Self {
+        Self::from_vec(par_iter.into_par_iter().collect())
+    }
+}
+
+/// Like the sequential `FromIterator<CellValue>`, elements with mixed cell-types are promoted
+/// to their [`CellType::union`][crate::CellType::union].
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+impl FromParallelIterator<CellValue> for CellBuffer {
+    fn from_par_iter<I: IntoParallelIterator<Item = CellValue>>(par_iter: I) -> Self {
+        let values: Vec<CellValue> = par_iter.into_par_iter().collect();
+        values.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BufferOps, CellBuffer, CellType, CellValue};
+    use rayon::prelude::*;
+
+    #[test]
+    fn par_min_max() {
+        for ct in CellType::iter().filter(|ct| CellType::UInt8.can_fit_into(*ct)) {
+            let buf = CellBuffer::fill_via(100_000, |i| (i % 97) as u8)
+                .convert(ct)
+                .unwrap();
+            assert_eq!(buf.par_min_max(), buf.min_max(), "{ct}");
+        }
+
+        let buf = CellBuffer::fill_via(100_000, |i| {
+            if i % 13 == 0 {
+                f64::NAN
+            } else {
+                (i as f64).sin()
+            }
+        });
+        assert_eq!(buf.par_min_max(), buf.min_max());
+
+        let empty = CellBuffer::with_defaults(0, CellType::Int16);
+        assert_eq!(empty.par_min_max(), empty.min_max());
+    }
+
+    #[test]
+    fn par_sum() {
+        let buf = CellBuffer::fill_via(100_000, |i| (i % 100) as u16);
+        assert_eq!(buf.par_sum(), CellValue::Float64(4_950_000.0));
+
+        let buf = CellBuffer::fill_via(1000, |i| i as f32 * 0.5);
+        let expected: f64 = (0..1000).map(|i| i as f64 * 0.5).sum();
+        assert!((buf.par_sum().get::<f64>().unwrap() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parity_1m() {
+        let buf = CellBuffer::fill_via(1_000_000, |i| (i % 1013) as i32 - 500);
+        assert_eq!(buf.par_min_max(), buf.min_max());
+        let seq: f64 = buf.into_iter().map(|v| v.get::<f64>().unwrap()).sum();
+        assert_eq!(buf.par_sum(), CellValue::Float64(seq));
+
+        let f = |v: CellValue| v * 2;
+        let par = buf.par_map_values(f);
+        let seq: CellBuffer = buf.into_iter().map(f).collect();
+        assert_eq!(par.cell_type(), CellType::Float64);
+        assert_eq!(par, seq);
+
+        let f = |v: CellValue| CellValue::new(v.get::<i32>().unwrap().unsigned_abs() as u16);
+        let seq: CellBuffer = buf.into_iter().map(f).collect();
+        assert_eq!(buf.par_map_values(f), seq);
+    }
+
+    #[test]
+    fn from_par_iter() {
+        let par: CellBuffer = (0..10_000u32).into_par_iter().map(|i| i as f32).collect();
+        let seq: CellBuffer = (0..10_000u32).map(|i| i as f32).collect();
+        assert_eq!(par, seq);
+
+        let par: CellBuffer = (0..10_000u32)
+            .into_par_iter()
+            .map(|i| CellValue::new(i as u16))
+            .collect();
+        let seq: CellBuffer = (0..10_000u32).map(|i| CellValue::new(i as u16)).collect();
+        assert_eq!(par.cell_type(), CellType::UInt16);
+        assert_eq!(par, seq);
+    }
+}
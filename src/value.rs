@@ -3,15 +3,21 @@ use crate::{
     error::{Error, Result},
     CellEncoding, CellType,
 };
-use num_traits::{One, ToPrimitive, Zero};
+#[cfg(feature = "f16")]
+use half::f16;
+use num_traits::{FromPrimitive, NumCast, One, ToPrimitive, Zero};
 use paste::paste;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
 /// CellValue enum constructor.
 macro_rules! cv_enum {
-    ( $(($id:ident, $p:ident)),*) => {
+    ( $(($id:ident, $p:ty)),*) => {
         /// Value variants for each [`CellType`]
+        ///
+        /// Non-exhaustive, as with [`CellType`].
+        #[non_exhaustive]
         #[derive(Debug, Copy, Clone)]
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         pub enum CellValue { $($id($p)),* }
@@ -23,9 +29,9 @@ impl CellValue {
     /// Construct new [`CellValue`] from a statically known [`CellEncoding`].
     pub fn new<T: CellEncoding + Sized>(value: T) -> Self {
         macro_rules! ctor {
-            ( $( ($id:ident, $p:ident) ),*) => {
+            ( $( ($id:ident, $p:ty) ),*) => {
                 match T::cell_type() {
-                    $(CellType::$id => CellValue::$id($p::static_cast(value).unwrap()),)*
+                    $(CellType::$id => CellValue::$id(<$p>::static_cast(value).unwrap()),)*
                 }
             };
         }
@@ -35,7 +41,7 @@ impl CellValue {
     /// Get the [`CellType`] encoding `self`.
     pub fn cell_type(&self) -> CellType {
         macro_rules! cv_ct {
-            ($( ($id:ident, $_p:ident) ),*) => {
+            ($( ($id:ident, $_p:ty) ),*) => {
                 match self {
                     $(CellValue::$id(_) => CellType::$id),*
                 }
@@ -56,7 +62,7 @@ impl CellValue {
         let cv = self.convert(T::cell_type())?;
 
         macro_rules! conv {
-             ($( ($id:ident, $_p:ident) ),*) => {
+             ($( ($id:ident, $_p:ty) ),*) => {
                  match cv {
                      $(CellValue::$id(v) => T::static_cast(v).ok_or_else(err),)*
                  }
@@ -85,18 +91,228 @@ impl CellValue {
         }
 
         macro_rules! convert {
-            ($( ($id:ident, $p:ident) ),*) => { paste! {
-
+            ($( ($id:ident, $p:ty) ),*) => {
                 match cell_type {
                     $(
-                      CellType::$id => Ok(self.[<to_ $p>]().ok_or_else(err)?.into_cell_value()),
+                      CellType::$id => Ok(<$p as NumCast>::from(*self).ok_or_else(err)?.into_cell_value()),
                     )*
                 }
-            }}
+            }
         }
         with_ct!(convert)
     }
 
+    /// Convert `self` into a variant with [`CellType`] `cell_type`, saturating rather than
+    /// failing when `cell_type` is narrower than the current `CellType`.
+    ///
+    /// Conversions that [`Self::convert`] supports give identical results. Otherwise:
+    /// * floating-point values converted to an integral type are first rounded to the nearest
+    ///   integer, with half-way cases rounded away from `0.0`, and `NaN` becomes `0`;
+    /// * values outside the range of `cell_type` saturate to its minimum or maximum value,
+    ///   except that infinities and `NaN` are preserved when `cell_type` is floating-point.
+    pub fn convert_clamped(&self, cell_type: CellType) -> Self {
+        if let Ok(v) = self.convert(cell_type) {
+            return v;
+        }
+        let v = self.round();
+        let is_float = !v.cell_type().is_integral();
+        let non_finite = is_float && !v.to_f64().unwrap().is_finite();
+        let (lo, hi) = (cell_type.min_value(), cell_type.max_value());
+        let v = match (non_finite, cell_type.is_integral()) {
+            (true, true) if v.to_f64().unwrap().is_nan() => return cell_type.zero(),
+            (true, false) => v,
+            _ => v.clamp(lo, hi),
+        };
+        macro_rules! convert_clamped {
+            ($( ($id:ident, $p:ty) ),*) => {
+                match cell_type {
+                    // Values at the boundary of `lo` or `hi` may still fail to cast due to
+                    // rounding when compared in `f64`.
+                    $(CellType::$id => <$p as NumCast>::from(v)
+                        .map(CellValue::$id)
+                        .unwrap_or(if v > cell_type.zero() { hi } else { lo }),)*
+                }
+            };
+        }
+        with_ct!(convert_clamped)
+    }
+
+    /// Convert `self` to an [`f16`](struct@f16), as the `ToPrimitive` counterpart for `Float16`.
+    ///
+    /// Values outside the range of `f16` become infinite.
+    #[cfg(feature = "f16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "f16")))]
+    pub fn to_f16(&self) -> Option<f16> {
+        self.to_f64().map(f16::from_f64)
+    }
+
+    /// Encode the value as its little-endian byte representation.
+    ///
+    /// The result has length `self.cell_type().size_of()`.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        macro_rules! to_bytes {
+            ($( ($id:ident, $_p:ty) ),*) => {
+                match self {
+                    $(CellValue::$id(v) => v.to_le_bytes().to_vec(),)*
+                }
+            };
+        }
+        with_ct!(to_bytes)
+    }
+
+    /// Encode the value as its big-endian byte representation.
+    ///
+    /// The result has length `self.cell_type().size_of()`.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        macro_rules! to_bytes {
+            ($( ($id:ident, $_p:ty) ),*) => {
+                match self {
+                    $(CellValue::$id(v) => v.to_be_bytes().to_vec(),)*
+                }
+            };
+        }
+        with_ct!(to_bytes)
+    }
+
+    /// Decode a [`CellValue`] of type `ct` from its little-endian byte representation.
+    ///
+    /// Returns `Err(ByteLengthError)` if `N != ct.size_of()`.
+    pub fn from_le_bytes<const N: usize>(bytes: [u8; N], ct: CellType) -> Result<Self> {
+        Self::from_le_slice(ct, &bytes)
+    }
+
+    /// Decode a [`CellValue`] of type `ct` from a slice holding its little-endian byte
+    /// representation, as with [`from_le_bytes`](Self::from_le_bytes).
+    ///
+    /// Returns `Err(ByteLengthError)` if `bytes.len() != ct.size_of()`.
+    pub fn from_le_slice(ct: CellType, bytes: &[u8]) -> Result<Self> {
+        macro_rules! from_bytes {
+            ($( ($id:ident, $p:ty) ),*) => {
+                match ct {
+                    $(CellType::$id => bytes
+                        .try_into()
+                        .map(|b| CellValue::$id(<$p>::from_le_bytes(b)))
+                        .map_err(|_| Error::ByteLengthError { ct, len: bytes.len() }),)*
+                }
+            };
+        }
+        with_ct!(from_bytes)
+    }
+
+    /// Decode a [`CellValue`] of type `ct` from its big-endian byte representation.
+    ///
+    /// Returns `Err(ByteLengthError)` if `N != ct.size_of()`.
+    pub fn from_be_bytes<const N: usize>(bytes: [u8; N], ct: CellType) -> Result<Self> {
+        Self::from_be_slice(ct, &bytes)
+    }
+
+    /// Decode a [`CellValue`] of type `ct` from a slice holding its big-endian byte
+    /// representation, as with [`from_be_bytes`](Self::from_be_bytes).
+    ///
+    /// Returns `Err(ByteLengthError)` if `bytes.len() != ct.size_of()`.
+    pub fn from_be_slice(ct: CellType, bytes: &[u8]) -> Result<Self> {
+        macro_rules! from_bytes {
+            ($( ($id:ident, $p:ty) ),*) => {
+                match ct {
+                    $(CellType::$id => bytes
+                        .try_into()
+                        .map(|b| CellValue::$id(<$p>::from_be_bytes(b)))
+                        .map_err(|_| Error::ByteLengthError { ct, len: bytes.len() }),)*
+                }
+            };
+        }
+        with_ct!(from_bytes)
+    }
+
+    /// Subtract `other` from `self`, retaining integer exactness for unsigned operands.
+    ///
+    /// When both operands are unsigned integral values narrower than 64 bits, the
+    /// difference is computed in the next-wider signed type (e.g. `UInt8 - UInt8 -> Int16`).
+    /// Otherwise, this is the same as `self - other`.
+    pub fn sub_signed(&self, other: &CellValue) -> CellValue {
+        let (lhs, rhs) = self.unify(other);
+        match (lhs, rhs) {
+            (CellValue::UInt8(l), CellValue::UInt8(r)) => CellValue::Int16(l as i16 - r as i16),
+            (CellValue::UInt16(l), CellValue::UInt16(r)) => CellValue::Int32(l as i32 - r as i32),
+            (CellValue::UInt32(l), CellValue::UInt32(r)) => CellValue::Int64(l as i64 - r as i64),
+            _ => self - *other,
+        }
+    }
+
+    /// Decompose a floating-point value into a normalized fraction and an integral power of two,
+    /// such that `self == fraction * 2^exponent` and `0.5 <= |fraction| < 1.0`.
+    ///
+    /// `Float32` values are promoted to `f64` before decomposition. Returns `None` for
+    /// integral cell-types. As with C's `frexp`, zero, infinite and `NaN` values are
+    /// returned as-is with an exponent of `0`.
+    pub fn frexp(&self) -> Option<(f64, i32)> {
+        if self.cell_type().is_integral() {
+            return None;
+        }
+        let v = self.to_f64()?;
+        if v == 0.0 || !v.is_finite() {
+            return Some((v, 0));
+        }
+        // Scale subnormals into the normal range.
+        let (v, adjust) = if v.is_subnormal() {
+            (v * 2f64.powi(54), -54)
+        } else {
+            (v, 0)
+        };
+        let bits = v.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i32 - 1022;
+        let fraction = f64::from_bits((bits & !(0x7ff << 52)) | (1022 << 52));
+        Some((fraction, exp + adjust))
+    }
+
+    /// Replace non-finite floating-point values with the given substitutes, in the manner of
+    /// NumPy's `nan_to_num`.
+    ///
+    /// Integral and finite floating-point values are returned unchanged. Substitutes are cast
+    /// to the value's floating-point type (e.g. `f32` for `Float32` values).
+    pub fn nan_to_num(&self, nan: f64, posinf: f64, neginf: f64) -> CellValue {
+        let sub = |v: f64| {
+            if v.is_nan() {
+                nan
+            } else if v > 0.0 {
+                posinf
+            } else {
+                neginf
+            }
+        };
+        match *self {
+            #[cfg(feature = "f16")]
+            CellValue::Float16(v) if !v.is_finite() => {
+                CellValue::Float16(f16::from_f64(sub(v.to_f64())))
+            }
+            CellValue::Float32(v) if !v.is_finite() => CellValue::Float32(sub(v as f64) as f32),
+            CellValue::Float64(v) if !v.is_finite() => CellValue::Float64(sub(v)),
+            o => o,
+        }
+    }
+
+    /// Linearly interpolate between `a` and `b`, computing `a + (b - a) * t` in `f64`.
+    ///
+    /// As with other arithmetic on [`CellValue`]s, the result is a `Float64`. `t` is not
+    /// clamped, so values outside `[0, 1]` extrapolate.
+    pub fn lerp(a: CellValue, b: CellValue, t: f64) -> CellValue {
+        let (a, b) = (a.to_f64().unwrap(), b.to_f64().unwrap());
+        CellValue::Float64(a + (b - a) * t)
+    }
+
+    /// Determine if `self` and `other` are equal within `epsilon`, for tolerating rounding
+    /// differences in floating-point values.
+    ///
+    /// Values are unified and compared in `f64`, except when both are integral, in which case
+    /// they must be equal exactly. As with `PartialEq`, `NaN` equals `NaN`.
+    pub fn approx_eq(&self, other: &CellValue, epsilon: f64) -> bool {
+        if self.cell_type().is_integral() && other.cell_type().is_integral() {
+            self == other
+        } else {
+            self == other || (self.to_f64().unwrap() - other.to_f64().unwrap()).abs() <= epsilon
+        }
+    }
+
     /// Determines the smallest cell-type that can contain `self` and `other`, and then
     /// converts values to that cell-type and returns a tuple of the converted values, i.e.
     /// `(convert(self), convert(other))`.
@@ -105,8 +321,94 @@ impl CellValue {
         // `unwrap` should be ok as it assumes `CellType::union` is correct.
         (self.convert(dest).unwrap(), other.convert(dest).unwrap())
     }
+
+    /// Determines the smallest cell-type that can contain all of `values`, via
+    /// [`CellType::common`], and returns it along with the values converted to it.
+    ///
+    /// The cell-type is `UInt8` when `values` is empty.
+    pub fn unify_all(values: &[CellValue]) -> (CellType, Vec<CellValue>) {
+        let dest =
+            CellType::common(values.iter().map(CellValue::cell_type)).unwrap_or(CellType::UInt8);
+        // `unwrap` should be ok as it assumes `CellType::union` is correct.
+        (
+            dest,
+            values.iter().map(|v| v.convert(dest).unwrap()).collect(),
+        )
+    }
 }
 
+/// Generates floating-point rounding methods that leave integral values unchanged.
+macro_rules! cv_rounding {
+    ($( ($op:ident, $desc:literal) ),*) => {
+        impl CellValue {
+            $(
+            #[doc = concat!("Round floating-point values ", $desc, ", preserving [`CellType`].")]
+            ///
+            /// Integral values are returned unchanged.
+            pub fn $op(&self) -> CellValue {
+                match *self {
+                    #[cfg(feature = "f16")]
+                    CellValue::Float16(v) => CellValue::Float16(num_traits::Float::$op(v)),
+                    CellValue::Float32(v) => CellValue::Float32(v.$op()),
+                    CellValue::Float64(v) => CellValue::Float64(v.$op()),
+                    o => o,
+                }
+            }
+            )*
+        }
+    };
+}
+cv_rounding!(
+    (
+        round,
+        "to the nearest integer, with half-way cases away from `0.0`"
+    ),
+    (
+        floor,
+        "down to the largest integer less than or equal to the value"
+    ),
+    (
+        ceil,
+        "up to the smallest integer greater than or equal to the value"
+    ),
+    (trunc, "toward `0.0`, discarding the fractional part")
+);
+
+/// Generates overflow-checked integer arithmetic methods.
+macro_rules! cv_checked {
+    ($( ($op:ident, $desc:literal) ),*) => { paste! {
+        impl CellValue {
+            $(
+            #[doc = concat!("Checked integer ", $desc, ", without promoting to `f64`.")]
+            ///
+            /// Operands are first converted to their [unified][Self::unify] cell-type, which is
+            /// also the cell-type of the result. Returns `None` on overflow, division by zero,
+            /// or if the unified cell-type is floating-point (including e.g. `UInt64` with a
+            /// signed type, which only unify as `Float64`).
+            pub fn [<checked_ $op>](&self, rhs: CellValue) -> Option<CellValue> {
+                match self.unify(&rhs) {
+                    (CellValue::UInt8(l), CellValue::UInt8(r)) => l.[<checked_ $op>](r).map(CellValue::UInt8),
+                    (CellValue::UInt16(l), CellValue::UInt16(r)) => l.[<checked_ $op>](r).map(CellValue::UInt16),
+                    (CellValue::UInt32(l), CellValue::UInt32(r)) => l.[<checked_ $op>](r).map(CellValue::UInt32),
+                    (CellValue::UInt64(l), CellValue::UInt64(r)) => l.[<checked_ $op>](r).map(CellValue::UInt64),
+                    (CellValue::Int8(l), CellValue::Int8(r)) => l.[<checked_ $op>](r).map(CellValue::Int8),
+                    (CellValue::Int16(l), CellValue::Int16(r)) => l.[<checked_ $op>](r).map(CellValue::Int16),
+                    (CellValue::Int32(l), CellValue::Int32(r)) => l.[<checked_ $op>](r).map(CellValue::Int32),
+                    (CellValue::Int64(l), CellValue::Int64(r)) => l.[<checked_ $op>](r).map(CellValue::Int64),
+                    _ => None,
+                }
+            }
+            )*
+        }
+    }};
+}
+cv_checked!(
+    (add, "addition"),
+    (sub, "subtraction"),
+    (mul, "multiplication"),
+    (div, "division, rounding toward zero")
+);
+
 /// Convert from primitive to [`CellValue`].
 impl<T: CellEncoding> From<T> for CellValue {
     fn from(value: T) -> Self {
@@ -114,11 +416,39 @@ impl<T: CellEncoding> From<T> for CellValue {
     }
 }
 
+/// `TryFrom<CellValue>` for each primitive, delegating to [`CellValue::get`].
+macro_rules! cv_try_from {
+    ( $(($_id:ident, $p:ty)),*) => { $(
+        impl TryFrom<CellValue> for $p {
+            type Error = Error;
+            /// Returns `Err(NarrowingError)` if the value cannot be extracted without loss.
+            fn try_from(value: CellValue) -> Result<Self> {
+                value.get()
+            }
+        }
+    )* };
+}
+with_ct!(cv_try_from);
+
+/// Renders the contained primitive value, without any cell-type decoration.
+impl Display for CellValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        macro_rules! display {
+            ($( ($id:ident, $_p:ty) ),*) => {
+                match self {
+                    $(CellValue::$id(v) => Display::fmt(v, f),)*
+                }
+            }
+        }
+        with_ct!(display)
+    }
+}
+
 /// Provide `num_traits` interop.
 impl ToPrimitive for CellValue {
     fn to_i64(&self) -> Option<i64> {
         macro_rules! conv {
-            ($( ($id:ident, $_p:ident) ),*) => {
+            ($( ($id:ident, $_p:ty) ),*) => {
                 match self {
                     $(
                     CellValue::$id(v) => v.to_i64(),
@@ -131,7 +461,7 @@ impl ToPrimitive for CellValue {
 
     fn to_u64(&self) -> Option<u64> {
         macro_rules! conv {
-            ($( ($id:ident, $_p:ident) ),*) => {
+            ($( ($id:ident, $_p:ty) ),*) => {
                 match self {
                     $(
                     CellValue::$id(v) => v.to_u64(),
@@ -144,7 +474,7 @@ impl ToPrimitive for CellValue {
 
     fn to_f64(&self) -> Option<f64> {
         macro_rules! conv {
-            ($( ($id:ident, $_p:ident) ),*) => {
+            ($( ($id:ident, $_p:ty) ),*) => {
                 match self {
                     $(
                     CellValue::$id(v) => v.to_f64(),
@@ -156,6 +486,64 @@ impl ToPrimitive for CellValue {
     }
 }
 
+/// Provide `num_traits` interop.
+///
+/// As the source type of `from_i64`, `from_u64` and `from_f64` may be a narrowing
+/// conversion from a wider generic type, these construct a `Float64` value, consistent
+/// with the promotion of arithmetic to `f64`. The remaining methods construct the variant
+/// matching their primitive (e.g. `from_u8` constructs a `UInt8`).
+impl FromPrimitive for CellValue {
+    fn from_i64(n: i64) -> Option<Self> {
+        n.to_f64().map(CellValue::Float64)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        n.to_f64().map(CellValue::Float64)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        Some(CellValue::Float64(n))
+    }
+
+    fn from_i8(n: i8) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn from_i16(n: i16) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn from_i32(n: i32) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn from_u8(n: u8) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn from_u16(n: u16) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn from_u32(n: u32) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        Some(n.into())
+    }
+}
+
+/// Provide `num_traits` interop.
+///
+/// As the source type is only known to be `ToPrimitive`, values are converted via
+/// `to_f64` into a `Float64`.
+impl NumCast for CellValue {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        n.to_f64().map(CellValue::Float64)
+    }
+}
+
 impl One for CellValue {
     #[inline]
     fn one() -> Self {
@@ -171,7 +559,7 @@ impl Zero for CellValue {
 
     fn is_zero(&self) -> bool {
         macro_rules! zero {
-             ($( ($id:ident, $_p:ident) ),*) => {
+             ($( ($id:ident, $_p:ty) ),*) => {
                 match self {
                     $(
                     CellValue::$id(v) => v.is_zero(),
@@ -183,19 +571,99 @@ impl Zero for CellValue {
     }
 }
 
+/// Wrapper around [`CellValue`] serialized with separate `type` and `value` fields,
+/// e.g. `{"type": "Float64", "value": 2.5}`.
+///
+/// The default `CellValue` serialization is an externally tagged enum (e.g. `{"Float64": 2.5}`),
+/// which is awkward to consume from other languages.
+///
+/// # Example
+///
+/// ```rust
+/// use erased_cells::{CellValue, TaggedCellValue};
+/// let json = serde_json::to_string(&TaggedCellValue(CellValue::Int16(-3))).unwrap();
+/// assert_eq!(json, r#"{"type":"Int16","value":-3}"#);
+/// let TaggedCellValue(parsed) = serde_json::from_str(&json).unwrap();
+/// assert_eq!(parsed, CellValue::Int16(-3));
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TaggedCellValue(pub CellValue);
+
+#[cfg(feature = "serde")]
+mod tagged {
+    use super::{CellValue, TaggedCellValue};
+    use crate::with_ct;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    macro_rules! repr {
+        ($( ($id:ident, $p:ty) ),*) => {
+            /// Serialized form of [`TaggedCellValue`].
+            #[derive(Serialize, Deserialize)]
+            #[serde(tag = "type", content = "value")]
+            enum Repr { $($id($p)),* }
+
+            impl From<CellValue> for Repr {
+                fn from(value: CellValue) -> Self {
+                    match value {
+                        $(CellValue::$id(v) => Repr::$id(v),)*
+                    }
+                }
+            }
+
+            impl From<Repr> for CellValue {
+                fn from(value: Repr) -> Self {
+                    match value {
+                        $(Repr::$id(v) => CellValue::$id(v),)*
+                    }
+                }
+            }
+        };
+    }
+    with_ct!(repr);
+
+    impl Serialize for TaggedCellValue {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Repr::from(self.0).serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for TaggedCellValue {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Repr::deserialize(deserializer).map(|r| TaggedCellValue(r.into()))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<CellValue> for TaggedCellValue {
+    fn from(value: CellValue) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TaggedCellValue> for CellValue {
+    fn from(value: TaggedCellValue) -> Self {
+        value.0
+    }
+}
+
 pub(crate) mod ops {
     use std::{
         cmp::Ordering,
+        hash::{Hash, Hasher},
+        iter::{Product, Sum},
         ops::{Add, Div, Mul, Neg, Sub},
     };
 
-    use num_traits::ToPrimitive;
+    use num_traits::{One, ToPrimitive, Zero};
 
-    use crate::CellValue;
+    use crate::{with_ct, CellValue};
 
     // NOTE: We _currently_ take the position that any math ops will promote all integral primitives to f64 first
     // Will probably need to revisit this.
-    // TODO: figure out how to implement `<primitive> op <CellValue>` via `with_ct`.
     macro_rules! cv_bin_op {
         ($trt:ident, $mth:ident, $op:tt) => {
             impl <R> $trt<R> for &CellValue where R: Into<CellValue> {
@@ -221,6 +689,65 @@ pub(crate) mod ops {
     cv_bin_op!(Mul, mul, *);
     cv_bin_op!(Div, div, /);
 
+    // LHS scalar. The orphan rule requires a concrete impl for each primitive.
+    macro_rules! cv_lhs_scalar_op {
+        ($trt:ident, $mth:ident, $op:tt, $($p:ty),*) => { $(
+            impl $trt<CellValue> for $p {
+                type Output = CellValue;
+                fn $mth(self, rhs: CellValue) -> Self::Output {
+                    CellValue::from(self) $op rhs
+                }
+            }
+            impl $trt<&CellValue> for $p {
+                type Output = CellValue;
+                fn $mth(self, rhs: &CellValue) -> Self::Output {
+                    CellValue::from(self) $op *rhs
+                }
+            }
+        )* };
+    }
+    macro_rules! cv_lhs_scalar {
+        ( $(($_id:ident, $p:ty)),*) => {
+            cv_lhs_scalar_op!(Add, add, +, $($p),*);
+            cv_lhs_scalar_op!(Sub, sub, -, $($p),*);
+            cv_lhs_scalar_op!(Mul, mul, *, $($p),*);
+            cv_lhs_scalar_op!(Div, div, /, $($p),*);
+        };
+    }
+    with_ct!(cv_lhs_scalar);
+
+    /// Sums values with [`Add`], starting from [`CellValue::zero`].
+    ///
+    /// As with `Add`, values are promoted to `f64`, so the sum of a non-empty iterator is
+    /// a `Float64`, while an empty iterator sums to `UInt8(0)`.
+    impl Sum for CellValue {
+        fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+            iter.fold(CellValue::zero(), Add::add)
+        }
+    }
+
+    impl<'a> Sum<&'a CellValue> for CellValue {
+        fn sum<I: Iterator<Item = &'a CellValue>>(iter: I) -> Self {
+            iter.copied().sum()
+        }
+    }
+
+    /// Multiplies values with [`Mul`], starting from [`CellValue::one`].
+    ///
+    /// As with `Mul`, values are promoted to `f64`, so the product of a non-empty iterator
+    /// is a `Float64`, while an empty iterator yields `UInt8(1)`.
+    impl Product for CellValue {
+        fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+            iter.fold(CellValue::one(), Mul::mul)
+        }
+    }
+
+    impl<'a> Product<&'a CellValue> for CellValue {
+        fn product<I: Iterator<Item = &'a CellValue>>(iter: I) -> Self {
+            iter.copied().product()
+        }
+    }
+
     impl Neg for CellValue {
         type Output = CellValue;
         fn neg(self) -> Self::Output {
@@ -233,6 +760,8 @@ pub(crate) mod ops {
                 CellValue::Int16(v) => CellValue::new(-v),
                 CellValue::Int32(v) => CellValue::new(-v),
                 CellValue::Int64(v) => CellValue::new(-v),
+                #[cfg(feature = "f16")]
+                CellValue::Float16(v) => CellValue::new(-v),
                 CellValue::Float32(v) => CellValue::new(-v),
                 CellValue::Float64(v) => CellValue::new(-v),
             }
@@ -257,6 +786,8 @@ pub(crate) mod ops {
                 (CellValue::Int16(l), CellValue::Int16(r)) => Ord::cmp(&l, &r),
                 (CellValue::Int32(l), CellValue::Int32(r)) => Ord::cmp(&l, &r),
                 (CellValue::Int64(l), CellValue::Int64(r)) => Ord::cmp(&l, &r),
+                #[cfg(feature = "f16")]
+                (CellValue::Float16(l), CellValue::Float16(r)) => l.total_cmp(&r),
                 (CellValue::Float32(l), CellValue::Float32(r)) => l.total_cmp(&r),
                 (CellValue::Float64(l), CellValue::Float64(r)) => l.total_cmp(&r),
                 _ => unreachable!("{self:?} <> {other:?}"),
@@ -271,20 +802,32 @@ pub(crate) mod ops {
     }
 
     impl Eq for CellValue {}
+
+    /// Hashes the bit pattern of the value widened to `f64`.
+    ///
+    /// As equality compares values after unifying cell-types (so `UInt8(3) == Float64(3.0)`),
+    /// the hash must be independent of cell-type. Any values equal under unification have
+    /// identical `f64` representations, so `-0.0`/`+0.0` and `NaN`s hash consistently
+    /// with `total_cmp`-based equality.
+    impl Hash for CellValue {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            state.write_u64(self.to_f64().unwrap().to_bits());
+        }
+    }
 }
 
 #[cfg(test)]
-#[allow(illegal_floating_point_literal_pattern)]
 mod tests {
+    use crate::error::Error;
     use crate::with_ct;
     use crate::{CellType, CellValue};
-    use num_traits::{One, Zero};
+    use num_traits::{One, ToPrimitive, Zero};
 
     #[test]
     fn cell_type() {
         macro_rules! test {
-            ($( ($id:ident, $p:ident) ),*) => {
-                $(assert_eq!(CellValue::$id($p::default()).cell_type(), CellType::$id);)*
+            ($( ($id:ident, $p:ty) ),*) => {
+                $(assert_eq!(CellValue::$id(<$p>::default()).cell_type(), CellType::$id);)*
             };
         }
         with_ct!(test);
@@ -293,16 +836,16 @@ mod tests {
     #[test]
     fn get() {
         macro_rules! test {
-            ($( ($id:ident, $p:ident) ),*) => {
+            ($( ($id:ident, $p:ty) ),*) => {
                 $({
-                    let v = $p::default();
+                    let v = <$p>::default();
                     let cv = CellValue::new(v);
                     let r = cv.get::<$p>();
                     assert!(r.is_ok());
                     assert_eq!(r.unwrap(), v);
                     let r2 = cv.get::<f64>();
                     assert!(r2.is_ok(), "{:?}", cv);
-                    assert_eq!(r2.unwrap(), v as f64)
+                    assert_eq!(r2.unwrap(), ToPrimitive::to_f64(&v).unwrap())
                 })*
             }
         }
@@ -328,6 +871,324 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn display() {
+        assert_eq!(CellValue::UInt8(3).to_string(), "3");
+        assert_eq!(CellValue::Int16(-3).to_string(), "-3");
+        assert_eq!(CellValue::Float64(2.5).to_string(), "2.5");
+    }
+
+    #[test]
+    fn sub_signed() {
+        let r = CellValue::UInt8(3).sub_signed(&CellValue::UInt8(5));
+        assert!(matches!(r, CellValue::Int16(-2)));
+        let r = CellValue::UInt8(3).sub_signed(&CellValue::UInt16(5));
+        assert!(matches!(r, CellValue::Int32(-2)));
+        let r = CellValue::UInt32(0).sub_signed(&CellValue::UInt32(u32::MAX));
+        assert!(matches!(r, CellValue::Int64(v) if v == -(u32::MAX as i64)));
+        // Non-unsigned operands defer to `Sub`.
+        let r = CellValue::Int8(3).sub_signed(&CellValue::UInt8(5));
+        assert_eq!(r, CellValue::Float64(-2.0));
+    }
+
+    #[test]
+    fn frexp() {
+        assert_eq!(CellValue::Float64(8.0).frexp(), Some((0.5, 4)));
+        assert_eq!(CellValue::Float32(-3.0).frexp(), Some((-0.75, 2)));
+        assert_eq!(CellValue::Float64(0.1).frexp(), Some((0.8, -3)));
+        assert_eq!(CellValue::Float64(0.0).frexp(), Some((0.0, 0)));
+        assert_eq!(
+            CellValue::Float64(f64::INFINITY).frexp(),
+            Some((f64::INFINITY, 0))
+        );
+        let (f, e) = CellValue::Float64(f64::MIN_POSITIVE / 8.0).frexp().unwrap();
+        assert_eq!((f, e), (0.5, -1024));
+        assert_eq!(CellValue::UInt8(8).frexp(), None);
+    }
+
+    #[test]
+    fn from_primitive() {
+        use num_traits::{FromPrimitive, NumCast};
+        assert!(matches!(CellValue::from_f64(3.0), Some(CellValue::Float64(v)) if v == 3.0));
+        assert!(matches!(CellValue::from_i64(-3), Some(CellValue::Float64(v)) if v == -3.0));
+        assert!(matches!(CellValue::from_u8(3), Some(CellValue::UInt8(3))));
+        assert!(matches!(CellValue::from_f32(1.5), Some(CellValue::Float32(v)) if v == 1.5));
+        let v: Option<CellValue> = NumCast::from(7u8);
+        assert!(matches!(v, Some(CellValue::Float64(v)) if v == 7.0));
+        let v = <CellValue as NumCast>::from(7u8).unwrap();
+        assert_eq!(v.cell_type(), CellType::Float64);
+    }
+
+    #[test]
+    fn sum_product() {
+        let vals = || (1u8..=5).map(CellValue::UInt8);
+        let sum: CellValue = vals().sum();
+        assert!(matches!(sum, CellValue::Float64(s) if s == 15.0));
+        let product: CellValue = vals().product();
+        assert!(matches!(product, CellValue::Float64(p) if p == 120.0));
+
+        // Wider than `u8`, without overflow.
+        let sum: CellValue = [CellValue::UInt8(200), CellValue::UInt8(100)].iter().sum();
+        assert_eq!(sum, CellValue::Float64(300.0));
+
+        let empty = || std::iter::empty::<CellValue>();
+        assert!(matches!(empty().sum(), CellValue::UInt8(0)));
+        assert!(matches!(empty().product(), CellValue::UInt8(1)));
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::HashSet;
+        let set: HashSet<CellValue> = [
+            CellValue::UInt8(3),
+            CellValue::Int16(3),
+            CellValue::Float64(3.0),
+            CellValue::Float32(f32::NAN),
+            CellValue::Float64(f32::NAN as f64),
+            CellValue::Float64(0.0),
+            CellValue::Float64(-0.0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(&CellValue::Int64(3)));
+    }
+
+    #[test]
+    fn nan_to_num() {
+        let f = |v: CellValue| v.nan_to_num(0.0, 1e9, -1e9);
+        assert_eq!(f(CellValue::Float64(f64::NAN)), CellValue::Float64(0.0));
+        assert_eq!(
+            f(CellValue::Float64(f64::INFINITY)),
+            CellValue::Float64(1e9)
+        );
+        assert_eq!(
+            f(CellValue::Float32(f32::NEG_INFINITY)),
+            CellValue::Float32(-1e9)
+        );
+        assert_eq!(f(CellValue::Float32(2.5)), CellValue::Float32(2.5));
+        assert_eq!(f(CellValue::Int16(-7)), CellValue::Int16(-7));
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(u8::try_from(CellValue::UInt8(7)).unwrap(), 7);
+        assert!(matches!(
+            u8::try_from(CellValue::Int32(70000)),
+            Err(Error::NarrowingError {
+                src: CellType::Int32,
+                dst: CellType::UInt8
+            })
+        ));
+        assert_eq!(f64::try_from(CellValue::Int16(-3)).unwrap(), -3.0);
+        let v: i64 = CellValue::UInt32(9).try_into().unwrap();
+        assert_eq!(v, 9);
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(CellValue::Float64(2.6).round(), CellValue::Float64(3.0));
+        assert_eq!(CellValue::Float32(-2.5).round(), CellValue::Float32(-3.0));
+        assert_eq!(CellValue::Float64(2.6).floor(), CellValue::Float64(2.0));
+        assert_eq!(CellValue::Float32(2.1).ceil(), CellValue::Float32(3.0));
+        assert_eq!(CellValue::Float64(-2.6).trunc(), CellValue::Float64(-2.0));
+        assert_eq!(CellValue::UInt8(5).floor(), CellValue::UInt8(5));
+        assert_eq!(CellValue::Int32(-5).round().cell_type(), CellType::Int32);
+        assert!(CellValue::Float64(f64::NAN)
+            .round()
+            .to_f64()
+            .unwrap()
+            .is_nan());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tagged_serde() {
+        use crate::TaggedCellValue;
+        macro_rules! test {
+            ($( ($id:ident, $p:ty) ),*) => {$({
+                let v = TaggedCellValue(CellValue::$id(<$p>::one()));
+                let json = serde_json::to_string(&v).unwrap();
+                assert!(json.starts_with(concat!(r#"{"type":""#, stringify!($id), r#"","value":"#)), "{json}");
+                assert_eq!(serde_json::from_str::<TaggedCellValue>(&json).unwrap(), v);
+            })*};
+        }
+        with_ct!(test);
+
+        // Integers that fit several variants keep their cell-type.
+        let TaggedCellValue(v) = serde_json::from_str(r#"{"type":"UInt64","value":3}"#).unwrap();
+        assert_eq!(v.cell_type(), CellType::UInt64);
+        let TaggedCellValue(v) = serde_json::from_str(r#"{"type":"Float64","value":2.5}"#).unwrap();
+        assert_eq!(v, CellValue::Float64(2.5));
+        assert!(
+            serde_json::from_str::<TaggedCellValue>(r#"{"type":"UInt8","value":300}"#).is_err()
+        );
+        assert!(serde_json::from_str::<TaggedCellValue>(r#"{"type":"Bogus","value":1}"#).is_err());
+        // Default representation is unchanged.
+        assert_eq!(
+            serde_json::to_string(&CellValue::UInt8(1)).unwrap(),
+            r#"{"UInt8":1}"#
+        );
+    }
+
+    #[test]
+    fn checked_ops() {
+        use CellValue::*;
+        assert_eq!(UInt8(200).checked_add(UInt8(100)), None);
+        assert_eq!(UInt8(200).checked_add(UInt8(55)), Some(UInt8(255)));
+        assert_eq!(UInt8(1).checked_sub(UInt8(2)), None);
+        assert_eq!(Int16(1).checked_sub(UInt8(2)), Some(Int16(-1)));
+        // `UInt8` and `Int8` unify to `Int16`.
+        assert_eq!(UInt8(200).checked_mul(Int8(-100)), Some(Int16(-20000)));
+        assert_eq!(Int32(i32::MAX).checked_mul(Int32(2)), None);
+        assert_eq!(Int64(-7).checked_div(Int64(2)), Some(Int64(-3)));
+        assert_eq!(UInt32(7).checked_div(UInt32(0)), None);
+        assert_eq!(Int8(i8::MIN).checked_div(Int8(-1)), None);
+        assert_eq!(Float32(1.0).checked_add(Float32(1.0)), None);
+        assert_eq!(UInt8(1).checked_add(Float64(1.0)), None);
+        assert_eq!(UInt64(1).checked_add(Int8(1)), None);
+        assert_eq!(
+            UInt8(2).checked_add(UInt8(3)).unwrap().cell_type(),
+            CellType::UInt8
+        );
+    }
+
+    #[test]
+    fn unify_all() {
+        let (ct, vs) = CellValue::unify_all(&[CellValue::UInt8(3), CellValue::Float32(1.5)]);
+        assert_eq!(ct, CellType::Float32);
+        assert_eq!(vs, vec![CellValue::Float32(3.0), CellValue::Float32(1.5)]);
+        assert!(vs.iter().all(|v| v.cell_type() == ct));
+        let (ct, _) = CellValue::unify_all(&[CellValue::UInt8(3), CellValue::Int8(-1)]);
+        assert_eq!(ct, CellType::Int16);
+        assert_eq!(CellValue::unify_all(&[]), (CellType::UInt8, vec![]));
+    }
+
+    #[test]
+    fn convert_clamped() {
+        let f = |v: CellValue, ct: CellType| v.convert_clamped(ct);
+        assert_eq!(
+            f(CellValue::Float64(2.5), CellType::UInt8),
+            CellValue::UInt8(3)
+        );
+        assert_eq!(
+            f(CellValue::Float64(-2.5), CellType::Int8),
+            CellValue::Int8(-3)
+        );
+        assert_eq!(
+            f(CellValue::Float64(300.0), CellType::UInt8),
+            CellValue::UInt8(255)
+        );
+        assert_eq!(
+            f(CellValue::Float32(-1.0), CellType::UInt16),
+            CellValue::UInt16(0)
+        );
+        assert_eq!(
+            f(CellValue::Float64(f64::NAN), CellType::Int32),
+            CellValue::Int32(0)
+        );
+        assert_eq!(
+            f(CellValue::Float64(f64::INFINITY), CellType::Int64),
+            CellValue::Int64(i64::MAX)
+        );
+        assert_eq!(
+            f(CellValue::Float64(1e30), CellType::UInt64),
+            CellValue::UInt64(u64::MAX)
+        );
+        assert_eq!(
+            f(CellValue::Float64(u64::MAX as f64), CellType::UInt64),
+            CellValue::UInt64(u64::MAX)
+        );
+        assert_eq!(
+            f(CellValue::Int32(-70000), CellType::Int16),
+            CellValue::Int16(i16::MIN)
+        );
+        assert_eq!(
+            f(CellValue::UInt64(u64::MAX), CellType::Int64),
+            CellValue::Int64(i64::MAX)
+        );
+        assert_eq!(
+            f(CellValue::Int64(-1), CellType::UInt64),
+            CellValue::UInt64(0)
+        );
+        assert_eq!(
+            f(CellValue::Float64(1e300), CellType::Float32),
+            CellValue::Float32(f32::MAX)
+        );
+        assert_eq!(
+            f(CellValue::Float64(f64::NEG_INFINITY), CellType::Float32),
+            CellValue::Float32(f32::NEG_INFINITY)
+        );
+        assert!(f(CellValue::Float64(f64::NAN), CellType::Float32)
+            .to_f64()
+            .unwrap()
+            .is_nan());
+        // Widening is the same as `convert`.
+        assert_eq!(
+            f(CellValue::UInt8(7), CellType::Float32),
+            CellValue::Float32(7.0)
+        );
+
+        for src in CellType::iter() {
+            for dst in CellType::iter() {
+                for v in [src.min_value(), src.max_value(), src.zero()] {
+                    assert_eq!(f(v, dst).cell_type(), dst, "{v:?} -> {dst}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn approx_eq() {
+        let sum = CellValue::Float32(0.1) + CellValue::Float32(0.2);
+        assert!(sum.approx_eq(&CellValue::Float32(0.3), 1e-6));
+        let sum = CellValue::Float64(0.1 + 0.2);
+        assert_ne!(sum, CellValue::Float64(0.3));
+        assert!(sum.approx_eq(&CellValue::Float64(0.3), f64::EPSILON));
+        assert!(!sum.approx_eq(&CellValue::Float64(0.31), 1e-6));
+        assert!(CellValue::Float32(f32::NAN).approx_eq(&CellValue::Float64(f64::NAN), 0.0));
+        assert!(CellValue::UInt8(3).approx_eq(&CellValue::Float64(3.0000001), 1e-6));
+        assert!(!CellValue::UInt8(3).approx_eq(&CellValue::Int16(4), 2.0));
+    }
+
+    #[test]
+    fn lerp() {
+        let (a, b) = (CellValue::UInt8(0), CellValue::Int16(10));
+        assert_eq!(CellValue::lerp(a, b, 0.25), CellValue::Float64(2.5));
+        assert_eq!(CellValue::lerp(a, b, 0.0), a);
+        assert_eq!(CellValue::lerp(a, b, 1.0), b);
+        assert_eq!(CellValue::lerp(b, a, 1.5), CellValue::Float64(-5.0));
+    }
+
+    #[test]
+    fn bytes() {
+        let v = CellValue::from_le_bytes(1.5f32.to_le_bytes(), CellType::Float32).unwrap();
+        assert!(matches!(v, CellValue::Float32(1.5)));
+        let v = CellValue::from_be_bytes(1.5f32.to_be_bytes(), CellType::Float32).unwrap();
+        assert!(matches!(v, CellValue::Float32(1.5)));
+        let v = CellValue::from_be_bytes([0x01, 0x02], CellType::UInt16).unwrap();
+        assert!(matches!(v, CellValue::UInt16(0x0102)));
+        assert!(CellValue::from_le_bytes([0u8; 2], CellType::UInt8).is_err());
+        assert_eq!(CellValue::UInt16(0x0102).to_be_bytes(), vec![0x01, 0x02]);
+        assert_eq!(CellValue::UInt16(0x0102).to_le_bytes(), vec![0x02, 0x01]);
+
+        for ct in CellType::iter() {
+            let v = ct.max_value();
+            let bytes = v.to_le_bytes();
+            assert_eq!(bytes.len(), ct.size_of(), "{ct}");
+            assert_eq!(CellValue::from_le_slice(ct, &bytes).unwrap(), v, "{ct}");
+            let bytes = v.to_be_bytes();
+            assert_eq!(CellValue::from_be_slice(ct, &bytes).unwrap(), v, "{ct}");
+        }
+
+        let err = CellValue::from_le_slice(CellType::Float64, &[0u8; 4]).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ByteLengthError { ct: CellType::Float64, len: 4 }
+        ));
+        assert!(CellValue::from_be_slice(CellType::UInt8, &[0u8; 2]).is_err());
+    }
+
     #[test]
     fn zero_one() {
         assert!(CellValue::zero().is_zero());
@@ -345,6 +1206,17 @@ mod tests {
         assert!(matches!(-CellValue::Float32(1.0), CellValue::Float32(-1.0)));
     }
 
+    #[test]
+    fn lhs_scalar() {
+        let r = 5u8 - CellValue::UInt8(2);
+        assert!(matches!(r, CellValue::Float64(v) if v == 3.0));
+        let v = CellValue::Int16(4);
+        assert_eq!(1 + v, v + 1);
+        assert_eq!(2.0 * v, v * 2.0);
+        assert_eq!(10i64 / &v, CellValue::Float64(2.5));
+        assert_eq!(1.5f32 - v, CellValue::Float64(-2.5));
+    }
+
     #[test]
     fn binops() {
         let l = CellValue::UInt8(1);